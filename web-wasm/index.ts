@@ -21,10 +21,22 @@ import * as ECS from "./pkg";
 function update_transform_sys(transform: ECS.Transform, three_mesh: ECS.Mesh) {
   let mesh = meshes[three_mesh.mesh_index];
 
-  mesh.position.x = transform.position.x;
+  const { position, rotation, scale } = transform;
+  mesh.position.set(position.x, position.y, position.z);
+  mesh.quaternion.set(rotation.x, rotation.y, rotation.z, rotation.w);
+  mesh.scale.set(scale.x, scale.y, scale.z);
   // console.warn(mesh.position);
 }
 
+function tween_completed_sys(event: {
+  entity: number;
+  seq_index: number;
+  tween_index: number;
+  user_tag: number;
+}) {
+  console.log("tween completed", event);
+}
+
 // function animation_button_sys() {
 //   const button = document.getElementById("button") as HTMLElement;
 //   button.addEventListener("click", () => {
@@ -97,8 +109,10 @@ const startup = async () => {
 
   ecs.create_entity_with_mesh(meshes.length - 1);
 
-  ecs.add_system(RenderSys, true);
-  ecs.add_system(update_transform_sys, false);
+  // the render system draws what this frame's animations wrote
+  ecs.add_system(RenderSys, true, true);
+  ecs.add_system(update_transform_sys, false, false);
+  ecs.add_system(tween_completed_sys, false, false);
 };
 
 const startup2 = async () => {
@@ -0,0 +1,151 @@
+// `cargo bench --bench sequence`. playing a frame only looks at the tweens
+// around the playhead, so `play` stays flat however many keys a sequence has,
+// even under a tween as long as all of them, and so does `rewind` playing
+// backwards, while `seek`, which has to put every tween in place, grows with
+// them. the `_linear` groups play the same tweens in a `Par`, which still
+// goes through every child each frame like sequences did before their index
+
+use bevy_ecs::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::plugin::{AnimationPlugin, Plugin};
+
+#[derive(Component, Clone, Debug)]
+struct Value(f32);
+
+fn value(v: &mut Value) -> &mut f32 {
+    &mut v.0
+}
+
+const FRAME: f32 = 16.0;
+
+// `keys` tweens of 10ms one after the other
+fn sequence(keys: usize) -> Seq<Value> {
+    let mut seq = Seq::new();
+    for key in 0..keys {
+        let (from, to) = (key as f32, key as f32 + 1.0);
+        seq.then(Tween::new(
+            EasingFunction::QuadraticInOut,
+            10.0,
+            Lens::new(value, from, to),
+        ));
+    }
+    seq
+}
+
+// the same with one more tween over the whole sequence
+fn covered(keys: usize) -> Seq<Value> {
+    let mut seq = sequence(keys);
+    let total = seq.duration();
+    seq.insert(
+        0.0,
+        Tween::new(EasingFunction::Linear, total, Lens::new(value, 0.0, 1.0)),
+    );
+    seq
+}
+
+// the tweens of `sequence` side by side in a group
+fn linear(keys: usize) -> Par<Value> {
+    let mut par = Par::new();
+    for key in 0..keys {
+        let (from, to) = (key as f32, key as f32 + 1.0);
+        par.add_at(
+            key as f32 * 10.0,
+            Tween::new(
+                EasingFunction::QuadraticInOut,
+                10.0,
+                Lens::new(value, from, to),
+            ),
+        );
+    }
+    par
+}
+
+// one frame after the other through the whole timeline, back from its end
+// when `backwards`
+fn frames<S: Tweenable<Value>>(
+    c: &mut Criterion,
+    name: &str,
+    make: fn(usize) -> S,
+    backwards: bool,
+) {
+    let mut group = c.benchmark_group(name);
+    for keys in [10, 100, 1000, 10000] {
+        group.bench_with_input(BenchmarkId::from_parameter(keys), &keys, |b, &keys| {
+            let mut tweens = make(keys);
+            let total = tweens.duration();
+            let (first, step) = if backwards {
+                (total, -FRAME)
+            } else {
+                (0.0, FRAME)
+            };
+            let mut v = Value(0.0);
+            let mut time = first;
+            tweens.seek(&mut v, time);
+            b.iter(|| {
+                time += step;
+                if !(0.0..=total).contains(&time) {
+                    time = first;
+                    tweens.seek(&mut v, time);
+                }
+                tweens.sample(&mut v, black_box(time));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn play(c: &mut Criterion) {
+    frames(c, "play", sequence, false);
+    frames(c, "play_covered", covered, false);
+    frames(c, "play_linear", linear, false);
+    frames(c, "rewind", sequence, true);
+    frames(c, "rewind_linear", linear, true);
+}
+
+fn seek(c: &mut Criterion) {
+    let mut group = c.benchmark_group("seek");
+    for keys in [10, 100, 1000, 10000] {
+        group.bench_with_input(BenchmarkId::from_parameter(keys), &keys, |b, &keys| {
+            let mut seq = sequence(keys);
+            let total = seq.duration();
+            let mut v = Value(0.0);
+            let mut time = 0.0;
+            b.iter(|| {
+                // jumps all over the timeline
+                time = (time + total * 0.618) % total;
+                seq.seek(&mut v, black_box(time));
+            });
+        });
+    }
+    group.finish();
+}
+
+// one frame of the whole schedule with every entity playing its own sequence
+fn entities(c: &mut Criterion) {
+    let mut group = c.benchmark_group("entities");
+    group.sample_size(20);
+    for count in [1000, 100000] {
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, &count| {
+            let mut world = World::new();
+            let mut schedule = Schedule::new();
+            AnimationPlugin::<Value>::new().build(&mut world, &mut schedule);
+            world.resource_mut::<AnimationSystemInfo>().fixedDt = Some(FRAME);
+            let mut seq = sequence(100);
+            seq.repeat(Repeat::Infinite);
+            for _ in 0..count {
+                let mut animate = AnimateComponent::new();
+                animate.add_seq(seq.clone());
+                world.spawn((Value(0.0), animate));
+            }
+            world.resource_mut::<AnimationSystemInfo>().totalTime = f32::MAX;
+            schedule.run(&mut world);
+            b.iter(|| schedule.run(&mut world));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, play, seek, entities);
+criterion_main!(benches);
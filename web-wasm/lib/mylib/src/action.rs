@@ -0,0 +1,223 @@
+use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::animation::{field_bytes, EasingFunction, Tweenable};
+
+// what a `Call` runs
+enum Action<T> {
+    Rust(Arc<dyn Fn(&mut T) + Send + Sync>),
+    // a function given to `add_system`, called with the step's tag
+    Js(String),
+}
+
+impl<T> Clone for Action<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Action::Rust(f) => Action::Rust(f.clone()),
+            Action::Js(name) => Action::Js(name.clone()),
+        }
+    }
+}
+
+// a step that takes no time and runs an action when its sequence or group
+// goes through it: once per pass, on every cycle of a repeating sequence
+// and on the way back of a yoyo. a seek forward runs it once however many
+// cycles it skips, a seek back rewinds it without running it
+pub struct Call<T> {
+    // absolute
+    pub start_abs: f32,
+    pub user_tag: u32,
+    action: Action<T>,
+}
+
+impl<T> Call<T> {
+    pub fn new(f: impl Fn(&mut T) + Send + Sync + 'static) -> Self {
+        Self {
+            start_abs: 0.0,
+            user_tag: 0,
+            action: Action::Rust(Arc::new(f)),
+        }
+    }
+
+    // calls the JS system registered under `name`, nothing happens if there
+    // is none. the call waits for the frame to release the world, see
+    // `run_js_calls`
+    pub fn js(name: impl Into<String>) -> Self {
+        Self {
+            start_abs: 0.0,
+            user_tag: 0,
+            action: Action::Js(name.into()),
+        }
+    }
+
+    // given to the JS function and carried by the `TweenCompleted` events
+    pub fn with_tag(mut self, user_tag: u32) -> Self {
+        self.user_tag = user_tag;
+        self
+    }
+}
+
+impl<T: 'static> Tweenable<T> for Call<T> {
+    fn duration(&self) -> f32 {
+        0.0
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        self.start_abs = v;
+    }
+
+    fn lerp(&mut self, _comp: &mut T, _ratio: f32) {}
+
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn user_tag(&self) -> u32 {
+        self.user_tag
+    }
+
+    fn fire(&mut self, comp: &mut T) {
+        match &self.action {
+            Action::Rust(f) => f(comp),
+            Action::Js(name) => crate::queue_js_call(name, self.user_tag),
+        }
+    }
+
+    // only `fire` runs the action
+    fn writes(&self, _comp: &mut T) -> Option<Range<usize>> {
+        Some(0..0)
+    }
+}
+
+impl<T> Clone for Call<T> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            user_tag: self.user_tag,
+            action: self.action.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Call<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match &self.action {
+            Action::Rust(_) => "closure",
+            Action::Js(name) => name,
+        };
+        f.debug_struct("Call")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("user_tag", &self.user_tag)
+            .field("action", &action)
+            .finish()
+    }
+}
+
+// a step that takes no time and puts `value` in the `V` that `get` points
+// at. what was there before is kept and put back when the sequence goes
+// back before the step, until a reset
+pub struct Set<T, V> {
+    // absolute
+    pub start_abs: f32,
+    pub value: V,
+    get: fn(&mut T) -> &mut V,
+    previous: Option<V>,
+}
+
+impl<T, V> Set<T, V> {
+    pub fn new(get: fn(&mut T) -> &mut V, value: V) -> Self {
+        Self {
+            start_abs: 0.0,
+            value,
+            get,
+            previous: None,
+        }
+    }
+}
+
+impl<T: 'static, V: Clone + Debug + Send + Sync + 'static> Set<T, V> {
+    fn set(&mut self, comp: &mut T) {
+        let field = (self.get)(comp);
+        self.previous.get_or_insert_with(|| field.clone());
+        *field = self.value.clone();
+    }
+
+    fn restore(&mut self, comp: &mut T) {
+        if let Some(previous) = &self.previous {
+            *(self.get)(comp) = previous.clone();
+        }
+    }
+}
+
+impl<T, V> Tweenable<T> for Set<T, V>
+where
+    T: 'static,
+    V: Clone + Debug + Send + Sync + 'static,
+{
+    fn duration(&self) -> f32 {
+        0.0
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        self.start_abs = v;
+    }
+
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        if ratio >= 1.0 {
+            self.set(comp);
+        } else {
+            self.restore(comp);
+        }
+    }
+
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        if time >= self.start_abs {
+            self.set(comp);
+        } else {
+            self.restore(comp);
+        }
+    }
+
+    fn forget(&mut self) {
+        self.previous = None;
+    }
+
+    fn writes(&self, comp: &mut T) -> Option<Range<usize>> {
+        field_bytes(comp, |comp| Some((self.get)(comp)))
+    }
+}
+
+impl<T, V: Clone> Clone for Set<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            value: self.value.clone(),
+            get: self.get,
+            previous: self.previous.clone(),
+        }
+    }
+}
+
+impl<T, V: Debug> Debug for Set<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Set")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("value", &self.value)
+            .finish()
+    }
+}
@@ -1,41 +1,188 @@
+// field and variable names follow the JS side (currentTime, animationInfo...)
+#![allow(non_snake_case)]
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::ops::Range;
 
 use bevy_ecs::{
-    prelude::Component,
-    system::{Query, Res, ResMut, Resource},
+    prelude::{
+        Changed, Component, DetectChangesMut, Entity, EventWriter, Events, IntoSystemConfig,
+        Without, World,
+    },
+    schedule::Schedule,
+    system::{Query, Res, ResMut, Resource, SystemParam},
 };
 
 use dyn_clone::DynClone;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::{prelude::Closure, JsCast};
 use web_sys::console;
 
+use crate::layer::{adopt, blend, AdoptFn, AnimationLayer, Blend, BlendFn, BlendMode};
+use crate::plugin::AnimationSet;
 use crate::WORLD;
 
-macro_rules! add_to_animation_sys {
-    // macth like arm for macro
-    ($schedule:expr,$component:ty) => {{
-        $schedule.add_system(animation_controller_sys::<$component>);
-        $schedule.add_system(animation_sys::<$component>);
-    }};
-}
-
-pub trait Tweenable<T>: Send + Sync + DynClone + Debug {
+pub trait Tweenable<T>: Any + Send + Sync + DynClone + Debug {
     fn duration(&self) -> f32;
     fn start_abs(&self) -> f32;
     fn set_start_abs(&mut self, v: f32);
     fn lerp(&mut self, comp: &mut T, ratio: f32);
     fn ease(&self) -> EasingFunction;
+
+    // eased progress at an absolute time
+    fn ratio_at(&self, time: f32) -> f32 {
+        let duration = self.duration();
+        let r = if duration > 0.0 {
+            (time - self.start_abs()) / duration
+        } else if time < self.start_abs() {
+            0.0
+        } else {
+            1.0
+        };
+        easing_function_to_ratio(self.ease(), r)
+    }
+
+    // evaluate at an absolute time, groups override this to drive their children
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        let ratio = self.ratio_at(time);
+        self.lerp(comp, ratio);
+    }
+
+    // jump straight to `time`: the result only depends on `time`, never on
+    // what was sampled before. groups override this to re-evaluate children
+    fn seek(&mut self, comp: &mut T, time: f32) {
+        self.sample(comp, time)
+    }
+
+    // carried by the `TweenCompleted` events of this tweenable
+    fn user_tag(&self) -> u32 {
+        0
+    }
+
+    // additive tweens keep what they add to `comp` on the side: `unapply`
+    // takes it out before a frame is evaluated and `apply` puts it back
+    // after, see `RelativeLens`
+    fn unapply(&mut self, _comp: &mut T) {}
+    fn apply(&mut self, _comp: &mut T) {}
+
+    // drops the start values relative tweens captured, they capture again
+    // the next time they start
+    fn forget(&mut self) {}
+
+    // whether `unapply` and `apply` do anything, sequences only call them on
+    // the children that say so
+    fn additive(&self) -> bool {
+        false
+    }
+
+    // a sequence went through this child, see `Call`. runs once per pass,
+    // on the way back too when a sequence plays backwards
+    fn fire(&mut self, _comp: &mut T) {}
+
+    // absolute time of a label of this tweenable or of one of its children
+    fn label_time(&self, _name: &str) -> Option<f32> {
+        None
+    }
+
+    // the bytes of `comp` sampling this writes, see `field_bytes`. none when
+    // it isn't known, a sequence playing backwards then seeks every child
+    fn writes(&self, _comp: &mut T) -> Option<Range<usize>> {
+        None
+    }
 }
 
 pub type BoxedTweenable<T> = Box<dyn Tweenable<T>>;
 
-#[derive(Debug, Clone, Copy)]
+// where the field `get` gives sits in `comp`, as a range of bytes from its
+// start. none if it isn't inside `comp`
+pub fn field_bytes<T, V>(
+    comp: &mut T,
+    get: impl FnOnce(&mut T) -> Option<&mut V>,
+) -> Option<Range<usize>> {
+    let base = comp as *mut T as usize;
+    let field = get(comp)? as *mut V as usize;
+    let start = field.checked_sub(base)?;
+    let end = start + std::mem::size_of::<V>();
+    (end <= std::mem::size_of::<T>()).then_some(start..end)
+}
+
+impl<T: 'static> dyn Tweenable<T> {
+    // the tweenable behind a box, to change a `Spring` on the way for instance
+    pub fn downcast_mut<U: Tweenable<T>>(&mut self) -> Option<&mut U> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum EasingFunction {
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
     QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuarticIn,
+    QuarticOut,
+    QuarticInOut,
+    QuinticIn,
+    QuinticOut,
+    QuinticInOut,
+    ExponentialIn,
+    ExponentialOut,
+    ExponentialInOut,
+    CircularIn,
+    CircularOut,
+    CircularInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
     BounceOut,
+    BounceInOut,
+    // css `cubic-bezier(x1, y1, x2, y2)`
+    CubicBezier(f32, f32, f32, f32),
+    // css `steps(n, jump)`
+    Steps(u32, StepPosition),
+}
+
+// css `<step-position>`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StepPosition {
+    JumpStart,
+    JumpEnd,
+    JumpNone,
+    JumpBoth,
 }
 pub trait TweenTarget<T>: DynClone + Debug {
     fn lerp(&mut self, target: &mut T, ratio: f32);
+
+    // called instead of `lerp` while the tween hasn't started yet
+    fn unstarted(&mut self, target: &mut T, ratio: f32) {
+        self.lerp(target, ratio)
+    }
+
+    // see `Tweenable::unapply`, `Tweenable::apply`, `Tweenable::forget` and
+    // `Tweenable::additive`
+    fn unapply(&mut self, _target: &mut T) {}
+    fn apply(&mut self, _target: &mut T) {}
+    fn forget(&mut self) {}
+    fn additive(&self) -> bool {
+        false
+    }
+
+    // see `Tweenable::writes`
+    fn writes(&self, _target: &mut T) -> Option<Range<usize>> {
+        None
+    }
 }
 
 type BoxedTweenTarget<T> = Box<dyn TweenTarget<T> + Send + Sync + 'static>;
@@ -45,9 +192,10 @@ pub struct Tween<T> {
     pub ease: EasingFunction,
     pub duration: f32,
     pub target: BoxedTweenTarget<T>,
+    pub user_tag: u32,
 }
 
-impl<T> Tweenable<T> for Tween<T> {
+impl<T: 'static> Tweenable<T> for Tween<T> {
     fn duration(&self) -> f32 {
         self.duration
     }
@@ -67,6 +215,39 @@ impl<T> Tweenable<T> for Tween<T> {
     fn ease(&self) -> EasingFunction {
         self.ease
     }
+
+    fn user_tag(&self) -> u32 {
+        self.user_tag
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        let ratio = self.ratio_at(time);
+        if time < self.start_abs {
+            self.target.unstarted(comp, ratio)
+        } else {
+            self.target.lerp(comp, ratio)
+        }
+    }
+
+    fn unapply(&mut self, comp: &mut T) {
+        self.target.unapply(comp)
+    }
+
+    fn apply(&mut self, comp: &mut T) {
+        self.target.apply(comp)
+    }
+
+    fn forget(&mut self) {
+        self.target.forget()
+    }
+
+    fn additive(&self) -> bool {
+        self.target.additive()
+    }
+
+    fn writes(&self, comp: &mut T) -> Option<Range<usize>> {
+        self.target.writes(comp)
+    }
 }
 
 impl<T> Clone for Tween<T> {
@@ -76,6 +257,7 @@ impl<T> Clone for Tween<T> {
             ease: self.ease,
             duration: self.duration,
             target: dyn_clone::clone_box(&*self.target),
+            user_tag: self.user_tag,
         }
     }
 }
@@ -88,6 +270,7 @@ impl<T> std::fmt::Debug for Tween<T> {
             .field("ease", &self.ease)
             .field("duration", &self.duration)
             .field("target", &self.target)
+            .field("user_tag", &self.user_tag)
             .finish()
     }
 }
@@ -103,9 +286,17 @@ impl<T: 'static> Tween<T> {
             ease,
             duration,
             target: Box::new(target),
+            user_tag: 0,
         }
     }
 
+    // tells this tween's `TweenCompleted` events apart from the others. a
+    // `u32` so it reaches JS as a plain number, whatever its value
+    pub fn with_tag(mut self, user_tag: u32) -> Self {
+        self.user_tag = user_tag;
+        self
+    }
+
     pub fn then(&self, tween: impl Tweenable<T> + 'static) -> Seq<T> {
         let mut seq = Seq::new();
 
@@ -141,12 +332,16 @@ impl<T> Tweenable<T> for Delay {
         self.start_abs = v;
     }
 
-    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+    fn lerp(&mut self, _comp: &mut T, _ratio: f32) {
         // self.target.lerp(comp, ratio)
     }
 
     fn ease(&self) -> EasingFunction {
-        EasingFunction::BounceOut
+        EasingFunction::Linear
+    }
+
+    fn writes(&self, _comp: &mut T) -> Option<Range<usize>> {
+        Some(0..0)
     }
 }
 
@@ -162,100 +357,1581 @@ impl Delay {
         }
     }
 
-    pub fn then<T>(&self, tween: impl Tweenable<T> + 'static) -> Seq<T> {
-        let mut seq = Seq::new();
+    pub fn then<T: 'static>(&self, tween: impl Tweenable<T> + 'static) -> Seq<T> {
+        let mut seq = Seq::new();
+
+        seq.then(self.clone());
+        seq.then(tween);
+        seq
+    }
+    pub fn then_delay<T: 'static>(&self, duration: f32) -> Seq<T> {
+        let mut seq = Seq::new();
+
+        seq.then(self.clone());
+        seq.then_delay(duration);
+        seq
+    }
+}
+
+// plays every child whose span was crossed while moving forward from `from`
+// to `to`, so children that finished between two frames still land exactly
+// on their end state. going backwards is a seek: undoing a child can uncover
+// a field an earlier, already finished child has to write again
+fn advance_children<T: 'static>(
+    children: &mut [BoxedTweenable<T>],
+    comp: &mut T,
+    from: Option<f32>,
+    to: f32,
+) {
+    match from {
+        Some(from) if to == from => {}
+        Some(from) if to < from => seek_children(children, comp, to),
+        _ => {
+            let from = from.unwrap_or(f32::NEG_INFINITY);
+            for finished in [true, false] {
+                for child in children.iter_mut() {
+                    let start = child.start_abs();
+                    let end = start + child.duration();
+                    if end > from && start <= to && (end <= to) == finished {
+                        child.sample(comp, to);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// children that haven't started are put back at their start (last to
+// first), the others are sought to `time` in order. children that overlap
+// can write the same field: the ones that finished go first so the ones
+// still running win
+fn seek_children<T: 'static>(children: &mut [BoxedTweenable<T>], comp: &mut T, time: f32) {
+    for child in children.iter_mut().rev() {
+        if child.start_abs() > time {
+            child.seek(comp, f32::NEG_INFINITY);
+        }
+    }
+    for finished in [true, false] {
+        for child in children.iter_mut() {
+            let start = child.start_abs();
+            if start <= time && (start + child.duration() <= time) == finished {
+                child.seek(comp, time);
+            }
+        }
+    }
+}
+
+// fires the children played through from `from` to `to`, as a `Seq` does
+fn fire_children<T: 'static>(
+    children: &mut [BoxedTweenable<T>],
+    comp: &mut T,
+    from: Option<f32>,
+    to: f32,
+) {
+    let from = from.unwrap_or(f32::NEG_INFINITY);
+    for child in children.iter_mut() {
+        let start = child.start_abs();
+        let end = start + child.duration();
+        let crossed = if to > from {
+            from < end && end <= to
+        } else {
+            to <= start && start < from
+        };
+        if crossed {
+            child.fire(comp);
+        }
+    }
+}
+
+fn children_end<T: 'static>(children: &[BoxedTweenable<T>], start: f32) -> f32 {
+    children
+        .iter()
+        .map(|child| child.start_abs() + child.duration())
+        .fold(start, f32::max)
+}
+
+// the children of a `Seq`. the first frame a sequence plays indexes them by
+// start and by end, after that a frame only looks at the children running
+// and the ones starting, however long the others last. a frame going back
+// does the same once it knows what every child writes, see `Index::rewind`.
+// anything that can change them drops the index, the next frame builds it
+// again
+struct Tweens<T> {
+    list: Vec<BoxedTweenable<T>>,
+    index: Option<Index>,
+}
+
+#[derive(Debug, Clone)]
+struct Index {
+    // children by start, ties in the order they were added
+    order: Vec<usize>,
+    starts: Vec<f32>,
+    // end of `order[i]`
+    ends: Vec<f32>,
+    // positions in `order` by end, and those ends
+    by_end: Vec<usize>,
+    end_times: Vec<f32>,
+    // children `unapply` and `apply` do something for
+    additive: Vec<usize>,
+    // where the last frame left the playhead, see `Index::window`
+    at: Option<f32>,
+    // first position in `order` not started at `at`
+    next: usize,
+    // positions in `order` started but not ended at `at`, in order
+    active: Vec<usize>,
+    // worked out the first time a frame goes back, `None` inside when it
+    // has to seek every child
+    writes: Option<Option<Writes>>,
+}
+
+// what the children of an `Index` write, by byte of the component
+#[derive(Debug, Clone)]
+struct Writes {
+    // what `order[p]` writes
+    ranges: Vec<Range<usize>>,
+    // the positions writing each byte, in order
+    writers: Vec<Vec<usize>>,
+}
+
+impl Writes {
+    // none if a child doesn't say what it writes, or if the children weren't
+    // added in start order: `seek_children` goes by the order they were
+    // added, `Index::rewind` by start
+    fn new<T: 'static>(list: &[BoxedTweenable<T>], order: &[usize], comp: &mut T) -> Option<Self> {
+        if order.iter().enumerate().any(|(p, &i)| p != i) {
+            return None;
+        }
+        let ranges = order
+            .iter()
+            .map(|&i| list[i].writes(comp))
+            .collect::<Option<Vec<_>>>()?;
+        let mut writers = vec![Vec::new(); std::mem::size_of::<T>()];
+        for (p, range) in ranges.iter().enumerate() {
+            for byte in range.clone() {
+                writers[byte].push(p);
+            }
+        }
+        Some(Self { ranges, writers })
+    }
+}
+
+impl Index {
+    fn new<T: 'static>(list: &[BoxedTweenable<T>]) -> Self {
+        let mut order: Vec<usize> = (0..list.len()).collect();
+        order.sort_by(|&a, &b| list[a].start_abs().total_cmp(&list[b].start_abs()));
+        let starts = order.iter().map(|&i| list[i].start_abs()).collect();
+        let ends: Vec<f32> = order
+            .iter()
+            .map(|&i| list[i].start_abs() + list[i].duration())
+            .collect();
+        let mut by_end: Vec<usize> = (0..order.len()).collect();
+        by_end.sort_by(|&a, &b| ends[a].total_cmp(&ends[b]));
+        let end_times = by_end.iter().map(|&p| ends[p]).collect();
+        let additive = (0..list.len()).filter(|&i| list[i].additive()).collect();
+        Self {
+            order,
+            starts,
+            ends,
+            by_end,
+            end_times,
+            additive,
+            at: None,
+            next: 0,
+            active: Vec::new(),
+            writes: None,
+        }
+    }
+
+    fn end(&self) -> Option<f32> {
+        self.end_times.last().copied()
+    }
+
+    // the positions in `order` that can be running from `from` to `to`: the
+    // ones still running at `from` and the ones starting by `to`. playing on
+    // from where the last frame stopped only looks at those, anything else
+    // goes through every child with `Index::seek`
+    fn window(&mut self, from: f32, to: f32) -> Vec<usize> {
+        if self.at != Some(from) {
+            self.seek(from);
+        }
+        let mut window = std::mem::take(&mut self.active);
+        while self.next < self.starts.len() && self.starts[self.next] <= to {
+            window.push(self.next);
+            self.next += 1;
+        }
+        window
+    }
+
+    // what is left running once the frame got to `to`
+    fn played(&mut self, mut window: Vec<usize>, to: f32) {
+        window.retain(|&p| self.ends[p] > to);
+        self.active = window;
+        self.at = Some(to);
+    }
+
+    fn seek(&mut self, time: f32) {
+        self.next = self.starts.partition_point(|&start| start <= time);
+        self.active = (0..self.next).filter(|&p| self.ends[p] > time).collect();
+        self.at = Some(time);
+    }
+
+    // puts the children back from `from` to `to` the way `seek_children`
+    // does, only touching the ones that start or end in between and the
+    // finished ones that last wrote what those undo. false when it can't,
+    // the children have to be sought
+    fn rewind<T: 'static>(
+        &mut self,
+        list: &mut [BoxedTweenable<T>],
+        comp: &mut T,
+        from: f32,
+        to: f32,
+    ) -> bool {
+        if self.at != Some(from) {
+            return false;
+        }
+        let writes = self
+            .writes
+            .get_or_insert_with(|| Writes::new(list, &self.order, comp));
+        let Some(writes) = writes else {
+            return false;
+        };
+
+        // not started any more, back to their start last to first
+        let next = self.starts.partition_point(|&start| start <= to);
+        for p in (next..self.next).rev() {
+            list[self.order[p]].seek(comp, f32::NEG_INFINITY);
+        }
+
+        // what they wrote goes back to the last finished child writing it,
+        // and what that child writes to the last one writing that, and so on
+        let mut finished = Vec::new();
+        let mut bytes: Vec<usize> = (next..self.next)
+            .flat_map(|p| writes.ranges[p].clone())
+            .collect();
+        while let Some(byte) = bytes.pop() {
+            let writers = &writes.writers[byte];
+            let started = &writers[..writers.partition_point(|&p| p < next)];
+            let last = started.iter().rev().find(|&&p| self.ends[p] <= to);
+            if let Some(&p) = last {
+                if !finished.contains(&p) {
+                    finished.push(p);
+                    bytes.extend(writes.ranges[p].clone());
+                }
+            }
+        }
+        finished.sort_unstable();
+
+        // still running, or ended since `to`
+        let ended = self.end_times.partition_point(|&end| end <= to)
+            ..self.end_times.partition_point(|&end| end <= from);
+        let mut running: Vec<usize> = self
+            .active
+            .iter()
+            .chain(&self.by_end[ended])
+            .copied()
+            .filter(|&p| p < next)
+            .collect();
+        running.sort_unstable();
+
+        // the running ones last, they win over the finished ones
+        for &p in finished.iter().chain(&running) {
+            list[self.order[p]].seek(comp, to);
+        }
+        self.next = next;
+        self.active = running;
+        self.at = Some(to);
+        true
+    }
+}
+
+impl<T: 'static> Tweens<T> {
+    fn new() -> Self {
+        Self {
+            list: Vec::new(),
+            index: None,
+        }
+    }
+
+    fn push(&mut self, tween: BoxedTweenable<T>) {
+        self.index = None;
+        self.list.push(tween);
+    }
+
+    fn list_mut(&mut self) -> &mut [BoxedTweenable<T>] {
+        self.index = None;
+        &mut self.list
+    }
+
+    fn indexed(&mut self) -> (&mut [BoxedTweenable<T>], &mut Index) {
+        let index = self.index.get_or_insert_with(|| Index::new(&self.list));
+        (&mut self.list, index)
+    }
+
+    // latest end of a child, `start` if none ends later
+    fn end(&self, start: f32) -> f32 {
+        match &self.index {
+            Some(index) => index.end().map_or(start, |end| end.max(start)),
+            None => children_end(&self.list, start),
+        }
+    }
+
+    // plays the children from `from` to `to` like `advance_children`, only
+    // looking at the ones in the index window
+    fn advance(&mut self, comp: &mut T, from: Option<f32>, to: f32) {
+        match from {
+            Some(from) if to == from => {}
+            Some(from) if to < from => {
+                let (list, index) = self.indexed();
+                if !index.rewind(list, comp, from, to) {
+                    self.seek(comp, to);
+                }
+            }
+            _ => {
+                let from = from.unwrap_or(f32::NEG_INFINITY);
+                let (list, index) = self.indexed();
+                let window = index.window(from, to);
+                for finished in [true, false] {
+                    for &p in &window {
+                        let child = &mut list[index.order[p]];
+                        let start = child.start_abs();
+                        let end = start + child.duration();
+                        if end > from && start <= to && (end <= to) == finished {
+                            child.sample(comp, to);
+                        }
+                    }
+                }
+                index.played(window, to);
+            }
+        }
+    }
+
+    fn seek(&mut self, comp: &mut T, time: f32) {
+        let (list, index) = self.indexed();
+        seek_children(list, comp, time);
+        index.seek(time);
+    }
+
+    // calls `f` with the children in start order whose position in `order`
+    // `range` picks, every child in turn when there is no index yet
+    fn each_by_start(
+        &self,
+        range: impl FnOnce(&Index) -> std::ops::Range<usize>,
+        reverse: bool,
+        mut f: impl FnMut(usize, &BoxedTweenable<T>),
+    ) {
+        match &self.index {
+            Some(index) => {
+                let order = &index.order[range(index)];
+                if reverse {
+                    order.iter().rev().for_each(|&i| f(i, &self.list[i]));
+                } else {
+                    order.iter().for_each(|&i| f(i, &self.list[i]));
+                }
+            }
+            None if reverse => self
+                .list
+                .iter()
+                .enumerate()
+                .rev()
+                .for_each(|(i, c)| f(i, c)),
+            None => self.list.iter().enumerate().for_each(|(i, c)| f(i, c)),
+        }
+    }
+
+    // like `each_by_start` with the children ending from `from` to `to`,
+    // `from` included when `inclusive`
+    fn each_ending(
+        &self,
+        from: f32,
+        to: f32,
+        inclusive: bool,
+        mut f: impl FnMut(usize, &BoxedTweenable<T>),
+    ) {
+        match &self.index {
+            Some(index) => {
+                let first = if inclusive {
+                    index.end_times.partition_point(|&end| end < from)
+                } else {
+                    index.end_times.partition_point(|&end| end <= from)
+                };
+                let last = index.end_times.partition_point(|&end| end <= to);
+                let mut ending = index.by_end[first..last.max(first)].to_vec();
+                ending.sort_unstable();
+                for p in ending {
+                    let i = index.order[p];
+                    f(i, &self.list[i]);
+                }
+            }
+            None => self.list.iter().enumerate().for_each(|(i, c)| f(i, c)),
+        }
+    }
+}
+
+impl<T> Clone for Tweens<T> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self
+                .list
+                .iter()
+                .map(|tween| dyn_clone::clone_box(&**tween))
+                .collect(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Tweens<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(&self.list).finish()
+    }
+}
+
+// runs all of its children at once, lasts as long as the longest one
+pub struct Par<T> {
+    // absolute
+    pub start_abs: f32,
+    pub children: Vec<BoxedTweenable<T>>,
+    last_time: Option<f32>,
+}
+
+impl<T: 'static> Par<T> {
+    pub fn new() -> Self {
+        Self {
+            start_abs: 0.0,
+            children: Vec::new(),
+            last_time: None,
+        }
+    }
+
+    pub fn add(&mut self, tween: impl Tweenable<T> + 'static) -> &mut Self {
+        self.add_at(0.0, tween)
+    }
+
+    // starts the child `offset` ms after the group itself
+    pub fn add_at(&mut self, offset: f32, tween: impl Tweenable<T> + 'static) -> &mut Self {
+        self.children.push(Box::new(tween));
+        let last = self.children.last_mut().unwrap();
+        last.set_start_abs(self.start_abs + offset);
+        self
+    }
+}
+
+impl<T: 'static> Default for Par<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> Tweenable<T> for Par<T> {
+    fn duration(&self) -> f32 {
+        children_end(&self.children, self.start_abs) - self.start_abs
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        let delta = v - self.start_abs;
+        for child in &mut self.children {
+            child.set_start_abs(child.start_abs() + delta);
+        }
+        self.start_abs = v;
+        self.last_time = None;
+    }
+
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        let time = self.start_abs + self.duration() * ratio;
+        self.sample(comp, time);
+    }
+
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        let from = self.last_time;
+        match from {
+            Some(last) if last >= self.start_abs => {
+                advance_children(&mut self.children, comp, Some(last), time)
+            }
+            // just started, children that haven't yet go back to their start
+            _ => seek_children(&mut self.children, comp, time),
+        }
+        self.last_time = Some(time);
+        if from != Some(time) {
+            fire_children(&mut self.children, comp, from, time);
+        }
+    }
+
+    // like a `Seq`, only a seek forward fires
+    fn seek(&mut self, comp: &mut T, time: f32) {
+        let from = self.last_time;
+        seek_children(&mut self.children, comp, time);
+        self.last_time = Some(time);
+        if from.is_none_or(|from| from < time) {
+            fire_children(&mut self.children, comp, from, time);
+        }
+    }
+
+    fn unapply(&mut self, comp: &mut T) {
+        for child in self.children.iter_mut().rev() {
+            child.unapply(comp);
+        }
+    }
+
+    fn apply(&mut self, comp: &mut T) {
+        for child in &mut self.children {
+            child.apply(comp);
+        }
+    }
+
+    fn forget(&mut self) {
+        for child in &mut self.children {
+            child.forget();
+        }
+    }
+
+    fn additive(&self) -> bool {
+        self.children.iter().any(|child| child.additive())
+    }
+
+    fn label_time(&self, name: &str) -> Option<f32> {
+        self.children
+            .iter()
+            .find_map(|child| child.label_time(name))
+    }
+}
+
+impl<T> Clone for Par<T> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            children: self
+                .children
+                .iter()
+                .map(|child| dyn_clone::clone_box(&**child))
+                .collect(),
+            last_time: self.last_time,
+        }
+    }
+}
+
+impl<T: 'static> std::fmt::Debug for Par<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Par")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+// a parallel group where child `i` starts `i * each` ms after the group
+pub struct Stagger<T> {
+    pub each: f32,
+    par: Par<T>,
+}
+
+impl<T: 'static> Stagger<T> {
+    pub fn new(each: f32) -> Self {
+        Self {
+            each,
+            par: Par::new(),
+        }
+    }
+
+    // the same child `count` times, e.g. a shake that ripples out
+    pub fn repeat(each: f32, count: usize, tween: impl Tweenable<T> + Clone + 'static) -> Self {
+        let mut stagger = Self::new(each);
+        for _ in 0..count {
+            stagger.add(tween.clone());
+        }
+        stagger
+    }
+
+    pub fn add(&mut self, tween: impl Tweenable<T> + 'static) -> &mut Self {
+        let offset = self.par.children.len() as f32 * self.each;
+        self.par.add_at(offset, tween);
+        self
+    }
+
+    pub fn children(&self) -> &[BoxedTweenable<T>] {
+        &self.par.children
+    }
+}
+
+impl<T: 'static> Tweenable<T> for Stagger<T> {
+    fn duration(&self) -> f32 {
+        self.par.duration()
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.par.start_abs()
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        self.par.set_start_abs(v)
+    }
+
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        self.par.lerp(comp, ratio)
+    }
+
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        self.par.sample(comp, time)
+    }
+
+    fn seek(&mut self, comp: &mut T, time: f32) {
+        self.par.seek(comp, time)
+    }
+
+    fn unapply(&mut self, comp: &mut T) {
+        self.par.unapply(comp)
+    }
+
+    fn apply(&mut self, comp: &mut T) {
+        self.par.apply(comp)
+    }
+
+    fn forget(&mut self) {
+        self.par.forget()
+    }
+
+    fn additive(&self) -> bool {
+        self.par.additive()
+    }
+
+    fn label_time(&self, name: &str) -> Option<f32> {
+        self.par.label_time(name)
+    }
+}
+
+impl<T> Clone for Stagger<T> {
+    fn clone(&self) -> Self {
+        Self {
+            each: self.each,
+            par: self.par.clone(),
+        }
+    }
+}
+
+impl<T: 'static> std::fmt::Debug for Stagger<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stagger")
+            .field("each", &self.each)
+            .field("par", &self.par)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Repeat {
+    // number of extra cycles after the first one
+    Count(u32),
+    Infinite,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Repeat::Count(0)
+    }
+}
+
+// where `Seq::insert` puts a child, in ms. strings read like GSAP's
+// position parameter: "500" is from the start of the sequence, "+=200" and
+// "-=200" from its end, "<" and ">" the start and end of the child added
+// last, with an optional offset ("<100", ">-=50"), and anything else is a
+// label with an optional offset ("intro+=200")
+#[derive(Debug, Clone, PartialEq)]
+pub enum Position {
+    // from the start of the sequence
+    At(f32),
+    // from the end of the sequence, where `then` puts children
+    End(f32),
+    PreviousStart(f32),
+    PreviousEnd(f32),
+    // a label that doesn't exist yet is added at the end of the sequence
+    Label(String, f32),
+}
+
+impl From<f32> for Position {
+    fn from(time: f32) -> Self {
+        Position::At(time)
+    }
+}
+
+impl From<&str> for Position {
+    fn from(s: &str) -> Self {
+        let s = s.trim();
+        let previous = s
+            .strip_prefix('<')
+            .map(|rest| (rest, Position::PreviousStart as fn(f32) -> Position))
+            .or_else(|| {
+                s.strip_prefix('>')
+                    .map(|rest| (rest, Position::PreviousEnd as _))
+            });
+        if let Some((rest, position)) = previous {
+            if let Some(offset) = parse_offset(rest) {
+                return position(offset);
+            }
+        }
+        if s.starts_with("+=") || s.starts_with("-=") {
+            if let Some(offset) = parse_offset(s) {
+                return Position::End(offset);
+            }
+        }
+        if let Ok(time) = s.parse() {
+            return Position::At(time);
+        }
+        if let Some(split) = s.rfind("+=").or_else(|| s.rfind("-=")) {
+            if let (label, Some(offset)) = (&s[..split], parse_offset(&s[split..])) {
+                return Position::Label(label.to_string(), offset);
+            }
+        }
+        Position::Label(s.to_string(), 0.0)
+    }
+}
+
+impl From<String> for Position {
+    fn from(s: String) -> Self {
+        Position::from(s.as_str())
+    }
+}
+
+// "", "+=200", "-=200" or a plain number
+fn parse_offset(s: &str) -> Option<f32> {
+    if s.is_empty() {
+        Some(0.0)
+    } else if let Some(offset) = s.strip_prefix("+=") {
+        offset.trim().parse().ok()
+    } else if let Some(offset) = s.strip_prefix("-=") {
+        offset.trim().parse().ok().map(|offset: f32| -offset)
+    } else {
+        s.trim().parse().ok()
+    }
+}
+
+pub struct Seq<T> {
+    // absolute
+    pub start_abs: f32,
+    tweens: Tweens<T>,
+    pub repeat: Repeat,
+    // every other cycle plays backwards
+    pub yoyo: bool,
+    // pause between two cycles, holding the last state
+    pub repeat_delay: f32,
+    // from the start of the sequence
+    labels: HashMap<String, f32>,
+    last_time: Option<f32>,
+}
+
+impl<T: 'static> Seq<T> {
+    pub fn new() -> Self {
+        Self {
+            start_abs: 0.0,
+            tweens: Tweens::new(),
+            repeat: Repeat::Count(0),
+            yoyo: false,
+            repeat_delay: 0.0,
+            labels: HashMap::new(),
+            last_time: None,
+        }
+    }
+
+    pub fn repeat(&mut self, repeat: Repeat) -> &mut Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn yoyo(&mut self, yoyo: bool) -> &mut Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    pub fn repeat_delay(&mut self, delay: f32) -> &mut Self {
+        self.repeat_delay = delay;
+        self
+    }
+
+    // length of a single pass over the children
+    pub fn cycle_duration(&self) -> f32 {
+        self.end() - self.start_abs
+    }
+
+    fn total_duration(&self) -> f32 {
+        let cycle = self.cycle_duration();
+        match self.repeat {
+            Repeat::Count(count) => cycle * (count + 1) as f32 + self.repeat_delay * count as f32,
+            Repeat::Infinite => f32::INFINITY,
+        }
+    }
+
+    // the children in the order they were added, delays included
+    pub fn tweens(&self) -> &[BoxedTweenable<T>] {
+        &self.tweens.list
+    }
+
+    // e.g. to retarget a spring with `downcast_mut`. the sequence indexes
+    // its children again on the next frame, changing them costs as much as
+    // a seek
+    pub fn tweens_mut(&mut self) -> &mut [BoxedTweenable<T>] {
+        self.tweens.list_mut()
+    }
+
+    // absolute time at which the next `then` starts, where the child that
+    // ends last ends
+    fn end(&self) -> f32 {
+        self.tweens.end(self.start_abs)
+    }
+
+    // takes anything tweenable, including other sequences, which are re-based
+    // (with all of their children) to start at the end of this one
+    pub fn then(&mut self, mut tween: impl Tweenable<T> + 'static) -> &mut Self {
+        tween.set_start_abs(self.end());
+        self.tweens.push(Box::new(tween));
+        self
+    }
+    pub fn then_delay(&mut self, duration: f32) -> &mut Self {
+        let mut delay = Delay::new(duration);
+
+        delay.start_abs = self.end();
+        self.tweens.push(Box::new(delay));
+        self
+    }
+
+    // like `then` but at `position`, children can overlap. a position
+    // before the start of the sequence is its start
+    pub fn insert(
+        &mut self,
+        position: impl Into<Position>,
+        mut tween: impl Tweenable<T> + 'static,
+    ) -> &mut Self {
+        tween.set_start_abs(self.resolve(position.into()));
+        self.tweens.push(Box::new(tween));
+        self
+    }
+
+    // names the end of the sequence
+    pub fn add_label(&mut self, name: impl Into<String>) -> &mut Self {
+        self.add_label_at(name, Position::End(0.0))
+    }
+
+    pub fn add_label_at(
+        &mut self,
+        name: impl Into<String>,
+        position: impl Into<Position>,
+    ) -> &mut Self {
+        let time = self.resolve(position.into()) - self.start_abs;
+        self.labels.insert(name.into(), time);
+        self
+    }
+
+    // absolute time of a label, in the first cycle. the sequence's own
+    // labels come before those of its children
+    pub fn label_time(&self, name: &str) -> Option<f32> {
+        match self.labels.get(name) {
+            Some(time) => Some(self.start_abs + time),
+            None => self
+                .tweens
+                .list
+                .iter()
+                .find_map(|tween| tween.label_time(name)),
+        }
+    }
+
+    // absolute time of `position`
+    fn resolve(&mut self, position: Position) -> f32 {
+        let previous = self.tweens.list.last();
+        let time = match position {
+            Position::At(time) => self.start_abs + time,
+            Position::End(offset) => self.end() + offset,
+            Position::PreviousStart(offset) => {
+                previous.map_or(self.start_abs, |last| last.start_abs()) + offset
+            }
+            Position::PreviousEnd(offset) => {
+                previous.map_or(self.start_abs, |last| last.start_abs() + last.duration()) + offset
+            }
+            Position::Label(name, offset) => {
+                let end = self.end() - self.start_abs;
+                self.start_abs + *self.labels.entry(name).or_insert(end) + offset
+            }
+        };
+        time.max(self.start_abs)
+    }
+
+    // the cycle `time` falls into, and the matching absolute time on the children
+    fn cycle_time(&self, time: f32) -> (u32, f32) {
+        let cycle = self.cycle_duration();
+        let period = cycle + self.repeat_delay;
+        let rel = time - self.start_abs;
+        if rel <= 0.0 || period <= 0.0 {
+            return (0, time);
+        }
+
+        let (index, phase) = match self.repeat {
+            // checked up front, rounding in `rel / period` could otherwise
+            // stop the last cycle just short of its end
+            Repeat::Count(count) if rel >= self.total_duration() => (count, cycle),
+            Repeat::Count(count) => {
+                let index = f32::min((rel / period).floor(), count as f32);
+                (index as u32, f32::min(rel - index * period, cycle))
+            }
+            Repeat::Infinite => {
+                let index = (rel / period).floor();
+                (index as u32, f32::min(rel - index * period, cycle))
+            }
+        };
+
+        let local = if self.is_reversed(index) {
+            cycle - phase
+        } else {
+            phase
+        };
+        (index, self.start_abs + local)
+    }
+
+    fn is_reversed(&self, index: u32) -> bool {
+        self.yoyo && index % 2 == 1
+    }
+
+    // children time at which cycle `index` starts or ends, in playback order
+    fn cycle_bound(&self, index: u32, end: bool) -> f32 {
+        if end != self.is_reversed(index) {
+            self.end()
+        } else {
+            self.start_abs
+        }
+    }
+
+    // what playing from `from` to `to` went through, in order: children that
+    // completed, cycles that were entered and the end of the sequence.
+    // anything played backwards completes when it gets back to its start.
+    // `once` goes straight to the last cycle after a whole one, a seek over
+    // many cycles has nothing more to find in the ones in between
+    fn crossings(&self, from: Option<f32>, to: f32, once: bool, mut on: impl FnMut(Crossing)) {
+        let from = from.unwrap_or(f32::NEG_INFINITY);
+        if from == to {
+            return;
+        }
+        let forward = to > from;
+
+        let (first, first_local) = self.cycle_time(from);
+        let (last, last_local) = self.cycle_time(to);
+        if first == last {
+            self.children_crossings(first_local, last_local, false, &mut on);
+        } else {
+            let leave = self.cycle_bound(first, forward);
+            self.children_crossings(first_local, leave, false, &mut on);
+
+            let mut cycle = first;
+            let mut whole = false;
+            while cycle != last {
+                cycle = match forward {
+                    _ if once && whole => last,
+                    true => cycle + 1,
+                    false => cycle - 1,
+                };
+                whole = cycle != last;
+                on(Crossing::Looped(cycle));
+                let enter = self.cycle_bound(cycle, !forward);
+                let leave = if cycle == last {
+                    last_local
+                } else {
+                    self.cycle_bound(cycle, forward)
+                };
+                self.children_crossings(enter, leave, true, &mut on);
+            }
+        }
+
+        if let Repeat::Count(_) = self.repeat {
+            let end = self.start_abs + self.total_duration();
+            let completed = if forward {
+                from < end && end <= to
+            } else {
+                to <= self.start_abs && self.start_abs < from
+            };
+            if completed {
+                on(Crossing::Completed);
+            }
+        }
+    }
+
+    // fires the children played through, as they complete, and hands back
+    // everything crossed for the events. `once` fires each child at most
+    // once, see `jump`
+    fn fire_crossed(
+        &mut self,
+        comp: &mut T,
+        from: Option<f32>,
+        to: f32,
+        once: bool,
+    ) -> Vec<Crossing> {
+        let mut crossed = Vec::new();
+        let mut seen = vec![false; if once { self.tweens.list.len() } else { 0 }];
+        self.crossings(from, to, once, |crossing| match crossing {
+            Crossing::Tween(index) if once => {
+                if !std::mem::replace(&mut seen[index], true) {
+                    crossed.push(crossing);
+                }
+            }
+            _ => crossed.push(crossing),
+        });
+        for &crossing in &crossed {
+            if let Crossing::Tween(index) = crossing {
+                self.tweens.list[index].fire(comp);
+            }
+        }
+        crossed
+    }
+
+    // `sample` that also returns what was crossed
+    fn play(&mut self, comp: &mut T, time: f32) -> Vec<Crossing> {
+        let from = self.last_time;
+        if from.is_none_or(|last| last < self.start_abs) {
+            // just started, children that haven't yet go back to their start
+            return self.jump(comp, time);
+        }
+
+        let (cycle, local) = self.cycle_time(time);
+        match self.last_time.map(|last| self.cycle_time(last)) {
+            Some((last_cycle, last_local)) if last_cycle != cycle => {
+                // finish the cycle we were in, then jump to where the new one begins
+                let (leave, enter) = if cycle > last_cycle {
+                    (
+                        self.cycle_bound(last_cycle, true),
+                        self.cycle_bound(cycle, false),
+                    )
+                } else {
+                    (
+                        self.cycle_bound(last_cycle, false),
+                        self.cycle_bound(cycle, true),
+                    )
+                };
+                self.tweens.advance(comp, Some(last_local), leave);
+                self.tweens.advance(comp, Some(leave), enter);
+                self.tweens.advance(comp, Some(enter), local);
+            }
+            last => self.tweens.advance(comp, last.map(|(_, l)| l), local),
+        }
+        self.last_time = Some(time);
+        self.fire_crossed(comp, from, time, false)
+    }
+
+    // `seek` that also returns what was crossed. the children it goes
+    // through fire once, however many cycles it skips
+    fn jump(&mut self, comp: &mut T, time: f32) -> Vec<Crossing> {
+        let from = self.last_time;
+        let (_, local) = self.cycle_time(time);
+        self.tweens.seek(comp, local);
+        if time <= self.start_abs && from.is_some_and(|from| from > time) {
+            self.last_time = None;
+        } else {
+            self.last_time = Some(time);
+            if from.is_none_or(|from| from < time) {
+                return self.fire_crossed(comp, from, time, true);
+            }
+        }
+        Vec::new()
+    }
+
+    // `inclusive` when `from` is where a cycle begins, so a child ending
+    // right there still completes on every pass, even when the cycle was
+    // only just entered
+    fn children_crossings(
+        &self,
+        from: f32,
+        to: f32,
+        inclusive: bool,
+        on: &mut impl FnMut(Crossing),
+    ) {
+        if inclusive && to == from {
+            let range = |index: &Index| {
+                index.starts.partition_point(|&start| start < from)
+                    ..index.starts.partition_point(|&start| start <= from)
+            };
+            self.tweens.each_by_start(range, false, |index, child| {
+                if child.start_abs() == from && child.duration() == 0.0 {
+                    on(Crossing::Tween(index));
+                }
+            });
+        } else if to > from {
+            self.tweens
+                .each_ending(from, to, inclusive, |index, child| {
+                    let end = child.start_abs() + child.duration();
+                    if (from < end || inclusive && from == end) && end <= to {
+                        on(Crossing::Tween(index));
+                    }
+                });
+        } else if to < from {
+            let range = |index: &Index| {
+                index.starts.partition_point(|&start| start < to)
+                    ..index.starts.partition_point(|&start| start <= from)
+            };
+            self.tweens.each_by_start(range, true, |index, child| {
+                let start = child.start_abs();
+                if to <= start && (start < from || inclusive && start == from) {
+                    on(Crossing::Tween(index));
+                }
+            });
+        }
+    }
+}
+
+// see `Seq::crossings`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Crossing {
+    Tween(usize),
+    Looped(u32),
+    Completed,
+}
+
+impl<T: 'static> Default for Seq<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> Tweenable<T> for Seq<T> {
+    fn duration(&self) -> f32 {
+        self.total_duration()
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        let delta = v - self.start_abs;
+        for tween in self.tweens.list_mut() {
+            tween.set_start_abs(tween.start_abs() + delta);
+        }
+        self.start_abs = v;
+        self.last_time = None;
+    }
+
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        // an endless sequence has no meaningful ratio, scrub its first cycle
+        let duration = match self.repeat {
+            Repeat::Infinite => self.cycle_duration(),
+            Repeat::Count(_) => self.duration(),
+        };
+        let time = self.start_abs + duration * ratio;
+        self.sample(comp, time);
+    }
+
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        self.play(comp, time);
+    }
+
+    // a jump forward goes through the children in between, a jump back to
+    // the start rewinds the sequence so the next pass begins from scratch
+    fn seek(&mut self, comp: &mut T, time: f32) {
+        self.jump(comp, time);
+    }
+
+    fn unapply(&mut self, comp: &mut T) {
+        let (list, index) = self.tweens.indexed();
+        for &i in index.additive.iter().rev() {
+            list[i].unapply(comp);
+        }
+    }
+
+    fn apply(&mut self, comp: &mut T) {
+        let (list, index) = self.tweens.indexed();
+        for &i in &index.additive {
+            list[i].apply(comp);
+        }
+    }
+
+    // the index stays, forgetting changes no timing
+    fn forget(&mut self) {
+        for tween in &mut self.tweens.list {
+            tween.forget();
+        }
+    }
+
+    fn additive(&self) -> bool {
+        match &self.tweens.index {
+            Some(index) => !index.additive.is_empty(),
+            None => self.tweens.list.iter().any(|tween| tween.additive()),
+        }
+    }
+
+    fn label_time(&self, name: &str) -> Option<f32> {
+        Seq::label_time(self, name)
+    }
+}
+
+impl<T> Clone for Seq<T> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            tweens: self.tweens.clone(),
+            repeat: self.repeat,
+            yoyo: self.yoyo,
+            repeat_delay: self.repeat_delay,
+            labels: self.labels.clone(),
+            last_time: self.last_time,
+        }
+    }
+}
+
+impl<T: 'static> std::fmt::Debug for Seq<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Seq")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("tweens", &self.tweens)
+            .field("repeat", &self.repeat)
+            .field("yoyo", &self.yoyo)
+            .field("repeat_delay", &self.repeat_delay)
+            .field("labels", &self.labels)
+            .finish()
+    }
+}
+
+#[derive(Component)]
+pub struct AnimateComponent<T: Send + Sync + 'static> {
+    pub sequences: Vec<Seq<T>>,
+    // blended in order over what `sequences` give
+    pub layers: Vec<AnimationLayer<T>>,
+    // last `seeks` and `resets` counts of the clock driving this entity
+    seeks: u32,
+    resets: u32,
+    // the target as it was before any tween touched it, what `reset` restores
+    snapshot: Option<T>,
+    // with layers, what `sequences` give before the blend
+    pose: Option<T>,
+    // with layers, the snapshot with what was written from outside since
+    rest: Option<T>,
+    // with layers, what the blend wrote last
+    written: Option<T>,
+    // set by `add_layer`
+    blend: Option<(BlendFn<T>, AdoptFn<T>)>,
+}
+
+impl<T: Send + Sync + 'static> AnimateComponent<T> {
+    pub fn new() -> Self {
+        Self {
+            sequences: Vec::new(),
+            layers: Vec::new(),
+            seeks: 0,
+            resets: 0,
+            snapshot: None,
+            pose: None,
+            rest: None,
+            written: None,
+            blend: None,
+        }
+    }
+    pub fn add_seq(&mut self, seq: Seq<T>) -> &mut Self {
+        self.sequences.push(seq);
+        self
+    }
+
+    pub fn add_tween(&mut self, t: Tween<T>) -> &mut Self {
+        let mut s = Seq::new();
+        s.then(t);
+        self.sequences.push(s);
+        self
+    }
+
+    // end of the longest sequence or fade, infinite if one loops forever
+    pub fn total_time(&self) -> f32 {
+        self.total_time_from(0.0)
+    }
+
+    // the same once layers that haven't started yet start at `now`, see
+    // `crossfade`
+    pub fn total_time_from(&self, now: f32) -> f32 {
+        let layers = self.layers.iter().map(|layer| layer.end(now));
+        self.sequences
+            .iter()
+            .map(|seq| seq.start_abs() + seq.duration())
+            .chain(layers)
+            .fold(0.0, f32::max)
+    }
+
+    pub fn snapshot(&self) -> Option<&T> {
+        self.snapshot.as_ref()
+    }
 
-        seq.then(self.clone());
-        seq.then(tween);
-        seq
+    // absolute time of the label in the first of `sequences` that has it
+    pub fn label_time(&self, name: &str) -> Option<f32> {
+        self.sequences.iter().find_map(|seq| seq.label_time(name))
     }
-    pub fn then_delay<T>(&self, duration: f32) -> Seq<T> {
-        let mut seq = Seq::new();
+}
 
-        seq.then(self.clone());
-        seq.then_delay(duration);
-        seq
+// additive tweens are taken out of the target while the sequences are
+// evaluated and layered back on top afterwards, in whatever order the
+// sequences come
+fn step_all<T: 'static>(
+    sequences: &mut [Seq<T>],
+    target: &mut T,
+    time: f32,
+    index: &mut usize,
+    step: &mut impl FnMut(usize, &mut Seq<T>, &mut T, f32),
+) {
+    for seq in sequences.iter_mut().rev() {
+        seq.unapply(target);
+    }
+    for seq in sequences.iter_mut() {
+        step(*index, seq, target, time);
+        *index += 1;
+    }
+    for seq in sequences.iter_mut() {
+        seq.apply(target);
     }
 }
 
-#[derive(Debug)]
-pub struct Seq<T> {
-    pub tweens: Vec<BoxedTweenable<T>>,
+// a pose for sequences that played straight on the target so far, before
+// they got layered, is that target. the others start from the rest pose
+fn pose_or<T: Clone + 'static>(pose: Option<T>, rest: &T, target: &T, sequences: &[Seq<T>]) -> T {
+    match pose {
+        Some(pose) => pose,
+        None if sequences.iter().any(|seq| seq.last_time.is_some()) => target.clone(),
+        None => rest.clone(),
+    }
 }
 
-impl<T> Seq<T> {
-    pub fn new() -> Self {
-        Self { tweens: Vec::new() }
+impl<T: Clone + Send + Sync + 'static> AnimateComponent<T> {
+    // takes the snapshot `reset` goes back to, the systems call it the first
+    // time they see the entity. call it again to make the current state the
+    // new starting point
+    pub fn capture(&mut self, target: &T) {
+        self.snapshot = Some(target.clone());
+        self.rest = None;
     }
 
-    pub fn then(&mut self, tween: impl Tweenable<T> + 'static) -> &mut Self {
-        let start_time = if self.tweens.is_empty() {
-            0.0
-        } else {
-            let last = self.tweens.last().unwrap();
-            last.start_abs() + last.duration()
-        };
+    // puts every sequence in the state it has at `time`, wherever it was before
+    pub fn seek(&mut self, target: &mut T, time: f32) {
+        self.each_seq(target, time, |_, seq, target, time| seq.seek(target, time));
+    }
 
-        self.tweens.push(Box::new(tween));
-        let last = self.tweens.last_mut().unwrap();
-        last.set_start_abs(start_time);
-        self
+    // seeks to a label, see `label_time`. returns where that is, nothing
+    // happens if no sequence has the label
+    pub fn seek_to_label(&mut self, target: &mut T, name: &str) -> Option<f32> {
+        let time = self.label_time(name)?;
+        self.seek(target, time);
+        Some(time)
     }
-    pub fn then_delay(&mut self, duration: f32) -> &mut Self {
-        let start_time = if self.tweens.is_empty() {
-            0.0
-        } else {
-            let last = self.tweens.last().unwrap();
-            last.start_abs() + last.duration()
+
+    // puts the target back exactly as it was captured and rewinds every
+    // sequence, the next sample starts over from scratch
+    pub fn reset(&mut self, target: &mut T) {
+        if self.layers.is_empty() {
+            for seq in self.sequences.iter_mut().rev() {
+                seq.unapply(target);
+            }
+        }
+        if let Some(snapshot) = &self.snapshot {
+            *target = snapshot.clone();
+        }
+        self.pose = None;
+        self.rest = None;
+        self.written = None;
+        for layer in &mut self.layers {
+            layer.pose = None;
+        }
+        let layers = self
+            .layers
+            .iter_mut()
+            .flat_map(|layer| &mut layer.sequences);
+        for seq in self.sequences.iter_mut().chain(layers) {
+            seq.last_time = None;
+            seq.forget();
+        }
+    }
+
+    // runs `step` on every sequence with the time it's at, numbered like
+    // their events: `sequences` first, then layer after layer. without layers
+    // the sequences write to the target, with layers each layer writes to a
+    // pose of its own and the poses are blended into the target
+    fn each_seq(
+        &mut self,
+        target: &mut T,
+        time: f32,
+        mut step: impl FnMut(usize, &mut Seq<T>, &mut T, f32),
+    ) {
+        let mut index = 0;
+        let (blend, adopt) = match self.blend {
+            Some(blend) if !self.layers.is_empty() => blend,
+            _ => return step_all(&mut self.sequences, target, time, &mut index, &mut step),
         };
 
-        let mut delay = Delay::new(duration);
+        let snapshot = self.snapshot.get_or_insert_with(|| target.clone());
+        let rest = self.rest.get_or_insert_with(|| snapshot.clone());
+        // only what the layers animate is written again, the rest of the
+        // target stays as whatever else wrote it
+        if let Some(written) = &self.written {
+            adopt(rest, written, target);
+            let layers = self.layers.iter_mut().map(|layer| &mut layer.pose);
+            for pose in std::iter::once(&mut self.pose).chain(layers).flatten() {
+                adopt(pose, written, target);
+            }
+        }
+        let rest = rest.clone();
+        let mut pose = pose_or(self.pose.take(), &rest, target, &self.sequences);
+        step_all(&mut self.sequences, &mut pose, time, &mut index, &mut step);
+        let mut out = pose.clone();
+        self.pose = Some(pose);
+
+        for layer in &mut self.layers {
+            let start = *layer.start.get_or_insert(time);
+            let mut pose = pose_or(layer.pose.take(), &rest, target, &layer.sequences);
+            step_all(
+                &mut layer.sequences,
+                &mut pose,
+                time - start,
+                &mut index,
+                &mut step,
+            );
+            let weight = layer.weight_at(time);
+            if weight > 0.0 {
+                blend(&mut out, &rest, &pose, layer.mode, weight);
+            }
+            layer.pose = Some(pose);
+        }
+        self.written = Some(out.clone());
+        *target = out;
+    }
+}
 
-        delay.start_abs = start_time;
-        self.tweens.push(Box::new(delay));
+impl<T: Clone + Blend + Send + Sync + 'static> AnimateComponent<T> {
+    // blends `layer` over `sequences` and the layers added before it. from
+    // then on the target is only written by the blend
+    pub fn add_layer(&mut self, layer: AnimationLayer<T>) -> &mut Self {
+        self.blend = Some((blend::<T>, adopt::<T>));
+        self.layers.push(layer);
         self
     }
-}
 
-#[derive(Component)]
-pub struct AnimateComponent<T: Component> {
-    pub sequences: Vec<Seq<T>>,
+    // fades `layer` in over what plays now in `duration` ms, from the next
+    // frame on where `layer` starts playing. what a fully faded in override
+    // layer covers is dropped, and `sequences` are moved to a layer of their
+    // own so they get covered like the rest
+    pub fn crossfade(&mut self, mut layer: AnimationLayer<T>, duration: f32) -> &mut Self {
+        if let Some(top) = self.layers.iter().rposition(AnimationLayer::covers) {
+            self.layers.drain(..top);
+            self.sequences.clear();
+            self.pose = None;
+        }
+        if !self.sequences.is_empty() {
+            let mut base = AnimationLayer::new(BlendMode::Override, 1.0);
+            base.sequences = std::mem::take(&mut self.sequences);
+            base.pose = self.pose.take();
+            self.layers.insert(0, base);
+        }
+        layer.start = None;
+        layer.fade_in(duration);
+        self.add_layer(layer)
+    }
 }
 
-impl<T: Component> AnimateComponent<T> {
-    pub fn new() -> Self {
+impl<T: Clone + Send + Sync + 'static> Clone for AnimateComponent<T> {
+    fn clone(&self) -> Self {
         Self {
-            sequences: Vec::new(),
+            sequences: self.sequences.clone(),
+            layers: self.layers.clone(),
+            seeks: self.seeks,
+            resets: self.resets,
+            snapshot: self.snapshot.clone(),
+            pose: self.pose.clone(),
+            rest: self.rest.clone(),
+            written: self.written.clone(),
+            blend: self.blend,
         }
     }
-    pub fn add_seq(&mut self, seq: Seq<T>) -> &mut Self {
-        self.sequences.push(seq);
-        self
-    }
+}
 
-    pub fn add_tween(&mut self, t: Tween<T>) -> &mut Self {
-        let mut s = Seq::new();
-        s.then(t);
-        self.sequences.push(s);
-        self
+impl<T: Send + Sync + 'static> Default for AnimateComponent<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl<T: Component + std::fmt::Debug> std::fmt::Debug for AnimateComponent<T> {
+impl<T: std::fmt::Debug + Send + Sync + 'static> std::fmt::Debug for AnimateComponent<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Animator")
             .field("sequences", &self.sequences)
+            .field("layers", &self.layers)
             .finish()
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AnimationSystemState {
     Play,
     Pause,
     Reset,
-    GoToTimeWithUpdate,
-    GoToTimeWithoutUpdate,
+}
+
+// per-entity playback, entities without one follow `AnimationSystemInfo`.
+// the global resource stays the master clock: its `dt` drives every player
+// and pausing it pauses them all
+#[derive(Component, Debug)]
+pub struct AnimationPlayer {
+    pub time: f32,
+    pub state: AnimationSystemState,
+    // signed, negative values play backwards
+    pub speed: f32,
+    // updated by `animation_controller_sys`
+    pub total_time: f32,
+    // bumped by `seek` and `reset`
+    pub seeks: u32,
+    pub resets: u32,
+}
+
+impl AnimationPlayer {
+    pub fn new() -> Self {
+        Self {
+            time: 0.0,
+            state: AnimationSystemState::Play,
+            speed: 1.0,
+            total_time: 0.0,
+            seeks: 0,
+            resets: 0,
+        }
+    }
+
+    pub fn play(&mut self) {
+        self.state = AnimationSystemState::Play;
+    }
+
+    pub fn pause(&mut self) {
+        self.state = AnimationSystemState::Pause;
+    }
+
+    // rewinds to the start and holds there until `play`. `total_time` is
+    // recomputed on the next frame so this is also the way to go after
+    // swapping the entity's sequences
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+        self.total_time = 0.0;
+        self.state = AnimationSystemState::Reset;
+        self.resets = self.resets.wrapping_add(1);
+    }
+
+    // jumps to `time` on the next frame, keeps playing or paused
+    pub fn seek(&mut self, time: f32) {
+        self.time = time;
+        self.seeks = self.seeks.wrapping_add(1);
+    }
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Resource, Debug)]
@@ -263,120 +1939,403 @@ pub struct AnimationSystemInfo {
     pub lastTime: f32,
     pub currentTime: f32,
     pub state: AnimationSystemState,
+    // measured by `animation_clock_sys` every frame
     pub dt: f32,
+    // longest step the clock takes, so resuming a background tab doesn't skip
+    // the whole animation
+    pub maxDt: f32,
+    // `now()` at the previous frame
+    pub lastFrame: Option<f64>,
+    // steps the clock by this every frame but the first instead of measuring
+    // it, for replays and tests that must not depend on the machine
+    pub fixedDt: Option<f32>,
+    // signed multiplier on `dt`, negative values play backwards
+    pub timeScale: f32,
+    // the longest of `totals`
     pub totalTime: f32,
+    // every component controller measures its total again this frame,
+    // cleared once they all have by `animation_updated_sys`
     pub needsUpdate: bool,
+    // how long the animations of every controller last, by what they animate
+    totals: HashMap<TypeId, f32>,
+    // bumped by `seek` and `reset`, every entity compares them to the last
+    // ones it applied
+    pub seeks: u32,
+    pub resets: u32,
+}
+
+impl AnimationSystemInfo {
+    // jumps to `time` on the next frame, keeps playing or paused
+    pub fn seek(&mut self, time: f32) {
+        self.currentTime = time;
+        self.seeks = self.seeks.wrapping_add(1);
+    }
+
+    // puts every entity back to its snapshot and holds there until played
+    pub fn reset(&mut self) {
+        self.currentTime = 0.0;
+        self.state = AnimationSystemState::Reset;
+        self.resets = self.resets.wrapping_add(1);
+    }
+
+    // replaces the total of a controller, `None` once it has nothing to play
+    fn set_total(&mut self, key: TypeId, total: Option<f32>) {
+        match total {
+            Some(total) => self.totals.insert(key, total),
+            None => self.totals.remove(&key),
+        };
+        self.totalTime = self.totals.values().copied().fold(0.0, f32::max);
+    }
 }
+
 impl Default for AnimationSystemInfo {
     fn default() -> Self {
         Self {
             lastTime: 0.0,
             currentTime: 0.0,
             state: AnimationSystemState::Play,
-            dt: 16.66,
+            dt: 0.0,
+            maxDt: 100.0,
+            lastFrame: None,
+            fixedDt: None,
+            timeScale: 1.0,
             totalTime: 0.0,
             needsUpdate: true,
+            totals: HashMap::new(),
+            seeks: 0,
+            resets: 0,
+        }
+    }
+}
+
+// milliseconds since an arbitrary origin, `performance.now()` in the browser
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or(0.0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> f64 {
+    lazy_static! {
+        static ref START: std::time::Instant = std::time::Instant::now();
+    }
+    START.elapsed().as_secs_f64() * 1000.0
+}
+
+// sent by `animation_sys` while playing, never by seeks or resets. a tween
+// played backwards completes when it gets back to its start.
+// `tween_index` indexes `Seq::tweens`, delays included. `seq_index` counts
+// `AnimateComponent::sequences` then the sequences of each layer in turn
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TweenCompleted {
+    pub entity: Entity,
+    pub seq_index: usize,
+    pub tween_index: usize,
+    pub user_tag: u32,
+}
+
+// the last cycle of a sequence ended (or its start was reached backwards),
+// sequences that loop forever never complete
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SequenceCompleted {
+    pub entity: Entity,
+    pub seq_index: usize,
+}
+
+// a repeating sequence entered cycle `cycle`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AnimationLooped {
+    pub entity: Entity,
+    pub seq_index: usize,
+    pub cycle: u32,
+}
+
+// there is no `App` to register the events, this adds them to the world
+// and the systems that rotate their buffers every frame to the schedule, with
+// the clock so what a frame sends can be read until the next one
+pub fn add_animation_events(world: &mut World, schedule: &mut Schedule) {
+    world.init_resource::<Events<TweenCompleted>>();
+    world.init_resource::<Events<SequenceCompleted>>();
+    world.init_resource::<Events<AnimationLooped>>();
+    schedule.add_system(Events::<TweenCompleted>::update_system.in_set(AnimationSet::Clock));
+    schedule.add_system(Events::<SequenceCompleted>::update_system.in_set(AnimationSet::Clock));
+    schedule.add_system(Events::<AnimationLooped>::update_system.in_set(AnimationSet::Clock));
+}
+
+#[derive(SystemParam)]
+pub struct AnimationEventWriters<'w> {
+    tween_completed: EventWriter<'w, TweenCompleted>,
+    sequence_completed: EventWriter<'w, SequenceCompleted>,
+    looped: EventWriter<'w, AnimationLooped>,
+}
+
+impl AnimationEventWriters<'_> {
+    fn send<T: 'static>(
+        &mut self,
+        entity: Entity,
+        seq_index: usize,
+        seq: &Seq<T>,
+        crossing: Crossing,
+    ) {
+        match crossing {
+            Crossing::Tween(tween_index) => self.tween_completed.send(TweenCompleted {
+                entity,
+                seq_index,
+                tween_index,
+                user_tag: seq.tweens.list[tween_index].user_tag(),
+            }),
+            Crossing::Looped(cycle) => self.looped.send(AnimationLooped {
+                entity,
+                seq_index,
+                cycle,
+            }),
+            Crossing::Completed => self
+                .sequence_completed
+                .send(SequenceCompleted { entity, seq_index }),
         }
     }
 }
 
 /////////////////////////// systems -------------------------/////////////////////////
 
-pub fn animation_sys<T: Component + Debug>(
-    mut animationInfo: ResMut<AnimationSystemInfo>,
-    mut query: Query<(&mut AnimateComponent<T>, &mut T)>,
+// what an entity plays, from its `AnimationPlayer` or `AnimationSystemInfo`
+struct Playback {
+    state: AnimationSystemState,
+    time: f32,
+    seeks: u32,
+    resets: u32,
+}
+
+impl From<&AnimationPlayer> for Playback {
+    fn from(player: &AnimationPlayer) -> Self {
+        Self {
+            state: player.state,
+            time: player.time,
+            seeks: player.seeks,
+            resets: player.resets,
+        }
+    }
+}
+
+impl From<&AnimationSystemInfo> for Playback {
+    fn from(info: &AnimationSystemInfo) -> Self {
+        Self {
+            state: info.state,
+            time: info.currentTime,
+            seeks: info.seeks,
+            resets: info.resets,
+        }
+    }
+}
+
+// applies `playback` to one entity
+fn run_animation_state<T: Clone + Send + Sync + 'static>(
+    entity: Entity,
+    events: &mut AnimationEventWriters,
+    animate: &mut AnimateComponent<T>,
+    target_component: &mut T,
+    playback: Playback,
 ) {
-    for (mut animate, mut target_component) in &mut query {
-        match animationInfo.state {
-            AnimationSystemState::Play => {
-                //play
-                for seq in &mut animate.sequences {
-                    for tween in &mut seq.tweens {
-                        if (tween.start_abs() <= animationInfo.currentTime
-                            && tween.start_abs() + tween.duration() >= animationInfo.currentTime)
-                        {
-                            let end = tween.start_abs() + tween.duration();
-
-                            let r = (animationInfo.currentTime - tween.start_abs())
-                                / (end - tween.start_abs());
-
-                            let ratio = easing_function_to_ratio(tween.ease(), r);
-                            // console::log_1(
-                            //     &format!("target_component{:?}", target_component).into(),
-                            // );
-
-                            tween.lerp(&mut target_component, ratio);
-                            break;
-                        }
-                    }
-                }
-            }
-            AnimationSystemState::Pause => return,
-            AnimationSystemState::Reset => {
-                for seq in &mut animate.sequences {
-                    let iter = seq.tweens.iter_mut().rev();
-                    for tween in iter {
-                        tween.lerp(&mut target_component, 0.0);
-                    }
-                }
+    if animate.snapshot.is_none() {
+        animate.capture(target_component);
+    }
 
-                animationInfo.state = AnimationSystemState::Pause;
-            }
-            AnimationSystemState::GoToTimeWithUpdate => {
-                for seq in &mut animate.sequences {
-                    for tween in &mut seq.tweens {
-                        if tween.start_abs() <= animationInfo.currentTime
-                            && tween.start_abs() + tween.duration() <= animationInfo.currentTime
-                        {
-                            tween.lerp(&mut target_component, 1.0);
-                        }
-                    }
-                }
-            }
-            AnimationSystemState::GoToTimeWithoutUpdate => {
-                for seq in &mut animate.sequences {
-                    for tween in &mut seq.tweens {
-                        if (tween.start_abs() <= animationInfo.currentTime
-                            && tween.start_abs() + tween.duration() >= animationInfo.currentTime)
-                        {
-                            let end = tween.start_abs() + tween.duration();
-                            let r = (animationInfo.currentTime - tween.start_abs())
-                                / (end - tween.start_abs());
-                            let ratio = easing_function_to_ratio(tween.ease(), r);
-
-                            tween.lerp(&mut target_component, ratio);
-                            break;
-                        }
-                    }
+    let time = playback.time;
+    if animate.seeks != playback.seeks {
+        animate.seeks = playback.seeks;
+        animate.seek(target_component, time);
+    }
+
+    match playback.state {
+        AnimationSystemState::Play => {
+            //play
+            animate.each_seq(target_component, time, |seq_index, seq, target, time| {
+                for crossing in seq.play(target, time) {
+                    events.send(entity, seq_index, seq, crossing);
                 }
+            });
+        }
+        AnimationSystemState::Pause => {}
+        AnimationSystemState::Reset => {
+            if animate.resets != playback.resets {
+                animate.resets = playback.resets;
+                animate.reset(target_component);
             }
+        }
+    };
+}
+
+pub fn animation_sys<T: Component + Clone + Debug>(
+    animationInfo: Res<AnimationSystemInfo>,
+    mut events: AnimationEventWriters,
+    mut query: Query<(
+        Entity,
+        &mut AnimateComponent<T>,
+        &mut T,
+        Option<&AnimationPlayer>,
+    )>,
+) {
+    for (entity, mut animate, mut target_component, player) in &mut query {
+        let playback = match player {
+            // the global clock still pauses every player
+            Some(_) if matches!(animationInfo.state, AnimationSystemState::Pause) => continue,
+            Some(player) => Playback::from(player),
+            None => Playback::from(&*animationInfo),
         };
+        // playing isn't a change, see `animation_controller_sys`
+        run_animation_state(
+            entity,
+            &mut events,
+            animate.bypass_change_detection(),
+            &mut target_component,
+            playback,
+        );
+    }
+}
 
-        if animationInfo.currentTime < 0.0 || animationInfo.currentTime > animationInfo.totalTime {
-            return;
+// an animation added or changed on the global clock, a retargeted spring for
+// instance, also makes the total be measured again
+pub fn animation_controller_sys<T: Component + Debug>(
+    mut animationInfo: ResMut<AnimationSystemInfo>,
+    mut query: Query<(&AnimateComponent<T>, Option<&mut AnimationPlayer>)>,
+    changed: Query<(), (Changed<AnimateComponent<T>>, Without<AnimationPlayer>)>,
+) {
+    // players only ever grow their total here, several `T`s can share one player
+    for (animate, player) in &mut query {
+        if let Some(mut player) = player {
+            let total_time = player.total_time.max(animate.total_time_from(player.time));
+            if total_time != player.total_time {
+                player.total_time = total_time;
+            }
         }
+    }
 
-        animationInfo.currentTime += animationInfo.dt;
+    if animationInfo.needsUpdate || !changed.is_empty() {
+        let mut total_time: f32 = 0.0;
+        for (animate, player) in &query {
+            if player.is_none() {
+                total_time = total_time.max(animate.total_time_from(animationInfo.currentTime));
+            }
+        }
+        animationInfo.set_total(TypeId::of::<AnimateComponent<T>>(), Some(total_time));
     }
 }
 
-pub fn animation_controller_sys<T: Component + Debug>(
+// animates the resource `T` like `AnimateComponent` animates a component,
+// on the global clock. a resource has no entity, the events its sequences
+// send carry `Entity::PLACEHOLDER`
+#[derive(Resource)]
+pub struct AnimateResource<T: Send + Sync + 'static>(pub AnimateComponent<T>);
+
+impl<T: Send + Sync + 'static> AnimateResource<T> {
+    pub fn new() -> Self {
+        Self(AnimateComponent::new())
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for AnimateResource<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + Sync + 'static> std::ops::Deref for AnimateResource<T> {
+    type Target = AnimateComponent<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> std::ops::DerefMut for AnimateResource<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: std::fmt::Debug + Send + Sync + 'static> std::fmt::Debug for AnimateResource<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AnimateResource").field(&self.0).finish()
+    }
+}
+
+// does nothing until both `T` and its `AnimateResource` are in the world
+pub fn animation_resource_sys<T: Resource + Clone>(
+    animationInfo: Res<AnimationSystemInfo>,
+    mut events: AnimationEventWriters,
+    animate: Option<ResMut<AnimateResource<T>>>,
+    target: Option<ResMut<T>>,
+) {
+    let (Some(mut animate), Some(mut target)) = (animate, target) else {
+        return;
+    };
+    run_animation_state(
+        Entity::PLACEHOLDER,
+        &mut events,
+        &mut animate.0,
+        &mut target,
+        Playback::from(&*animationInfo),
+    );
+}
+
+// makes room for the resource's animation on the global clock
+pub fn animation_resource_controller_sys<T: Resource>(
     mut animationInfo: ResMut<AnimationSystemInfo>,
-    mut query: Query<(&mut AnimateComponent<T>, &mut T)>,
+    animate: Option<Res<AnimateResource<T>>>,
 ) {
+    // measured every frame, so a replaced or removed animation shrinks it
+    let total_time = animate.map(|animate| animate.total_time_from(animationInfo.currentTime));
+    if animationInfo
+        .totals
+        .get(&TypeId::of::<AnimateResource<T>>())
+        != total_time.as_ref()
+    {
+        animationInfo.set_total(TypeId::of::<AnimateResource<T>>(), total_time);
+    }
+}
+
+// ends the frame once every controller has seen `needsUpdate`
+pub fn animation_updated_sys(mut animationInfo: ResMut<AnimationSystemInfo>) {
     if animationInfo.needsUpdate {
         animationInfo.needsUpdate = false;
+    }
+}
 
-        let mut total_time: f32 = 0.0;
-        for (mut animate, mut target_component) in &mut query {
-            //play
-            for seq in &mut animate.sequences {
-                for tween in &mut seq.tweens {
-                    total_time = total_time.max(tween.start_abs() + tween.duration());
-                }
-            }
+// measures the frame and advances the global time and every
+// `AnimationPlayer`, runs once per frame before any `animation_sys`
+pub fn animation_clock_sys(
+    mut animationInfo: ResMut<AnimationSystemInfo>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    let now = now();
+    let dt = match (animationInfo.lastFrame, animationInfo.fixedDt) {
+        (None, _) => 0.0,
+        (Some(_), Some(dt)) => dt,
+        (Some(last), None) => ((now - last) as f32).clamp(0.0, animationInfo.maxDt),
+    };
+    animationInfo.lastFrame = Some(now);
+    animationInfo.dt = dt;
+
+    match animationInfo.state {
+        AnimationSystemState::Pause => return,
+        AnimationSystemState::Reset => {}
+        _ => {
+            let time = animationInfo.currentTime + dt * animationInfo.timeScale;
+            animationInfo.currentTime = time.clamp(0.0, animationInfo.totalTime);
+        }
+    }
+
+    // players run on the master clock, so they pick up its scale too
+    let dt = dt * animationInfo.timeScale;
+    for mut player in &mut players {
+        if !matches!(player.state, AnimationSystemState::Play) {
+            continue;
         }
-        animationInfo.totalTime = total_time;
+        let time = player.time + dt * player.speed;
+        player.time = time.clamp(0.0, player.total_time);
     }
 }
 
@@ -408,16 +2367,15 @@ pub fn init_animations_buttons_sys() {
                         animationInfo.state = AnimationSystemState::Pause;
                         button.set_inner_html("PAUSE")
                     }
-                    AnimationSystemState::Pause => {
+                    AnimationSystemState::Pause | AnimationSystemState::Reset => {
                         animationInfo.state = AnimationSystemState::Play;
                         button.set_inner_html("Play")
                     }
-                    _ => {}
                 }
             });
 
             button
-                .add_event_listener_with_callback("click", &cb.as_ref().unchecked_ref())
+                .add_event_listener_with_callback("click", cb.as_ref().unchecked_ref())
                 .unwrap();
             cb.forget();
         }
@@ -443,12 +2401,11 @@ pub fn init_animations_buttons_sys() {
                     .unwrap();
                 button.set_inner_html("PLAY");
 
-                animationInfo.currentTime = 0.0;
-                animationInfo.state = AnimationSystemState::Reset;
+                animationInfo.reset();
             });
 
             reset
-                .add_event_listener_with_callback("click", &cb.as_ref().unchecked_ref())
+                .add_event_listener_with_callback("click", cb.as_ref().unchecked_ref())
                 .unwrap();
             cb.forget();
         }
@@ -479,15 +2436,15 @@ pub fn init_animations_buttons_sys() {
                 .map_err(|_| ())
                 .unwrap();
             match time_input_value.value().parse::<f32>() {
-                Ok(val) => animationInfo.currentTime = val,
+                Ok(val) => animationInfo.seek(val),
                 Err(err) => console::log_1(&format!("err{:?}", err).into()),
             };
 
-            animationInfo.state = AnimationSystemState::GoToTimeWithoutUpdate;
+            animationInfo.state = AnimationSystemState::Play;
         });
 
         gototime
-            .add_event_listener_with_callback("click", &cb.as_ref().unchecked_ref())
+            .add_event_listener_with_callback("click", cb.as_ref().unchecked_ref())
             .unwrap();
         cb.forget();
     }
@@ -519,15 +2476,15 @@ pub fn init_animations_buttons_sys() {
 
             animationInfo.lastTime = animationInfo.currentTime;
             match time_input_value.value().parse::<f32>() {
-                Ok(val) => animationInfo.currentTime = val,
+                Ok(val) => animationInfo.seek(val),
                 Err(err) => console::log_1(&format!("err{:?}", err).into()),
             };
 
-            animationInfo.state = AnimationSystemState::GoToTimeWithoutUpdate;
+            animationInfo.state = AnimationSystemState::Play;
         });
 
         gototimeupdate
-            .add_event_listener_with_callback("click", &cb.as_ref().unchecked_ref())
+            .add_event_listener_with_callback("click", cb.as_ref().unchecked_ref())
             .unwrap();
         cb.forget();
     }
@@ -549,22 +2506,211 @@ pub fn update_time(animationInfo: Res<AnimationSystemInfo>) {
 }
 
 pub fn easing_function_to_ratio(func: EasingFunction, val: f32) -> f32 {
+    use std::f32::consts::PI;
+
+    const BACK_C1: f32 = 1.70158;
+    const BACK_C2: f32 = BACK_C1 * 1.525;
+    const BACK_C3: f32 = BACK_C1 + 1.0;
+    const ELASTIC_C4: f32 = (2.0 * PI) / 3.0;
+    const ELASTIC_C5: f32 = (2.0 * PI) / 4.5;
+
+    let p = f32::clamp(val, 0.0, 1.0);
     match func {
-        EasingFunction::QuadraticIn => {
-            let v1 = f32::clamp(val, 0.0, 1.0);
-            v1 * v1
-        }
-        EasingFunction::BounceOut => {
-            let p = f32::clamp(val, 0.0, 1.0);
-            if (p < 4.0 / 11.0) {
-                return (121.0 * p * p) / 16.0;
-            } else if (p < 8.0 / 11.0) {
-                return (363.0 / 40.0) * p * p - (99.0 / 10.0) * p + 17.0 / 5.0;
-            } else if (p < 9.0 / 10.0) {
-                return ((4356.0 / 361.0) * p * p - (35442.0 / 1805.0) * p + 16061.0 / 1805.0);
+        EasingFunction::Linear => p,
+
+        EasingFunction::SineIn => 1.0 - ((p * PI) / 2.0).cos(),
+        EasingFunction::SineOut => ((p * PI) / 2.0).sin(),
+        EasingFunction::SineInOut => -((PI * p).cos() - 1.0) / 2.0,
+
+        EasingFunction::QuadraticIn => p * p,
+        EasingFunction::QuadraticOut => 1.0 - (1.0 - p) * (1.0 - p),
+        EasingFunction::QuadraticInOut => in_out(p, 2),
+
+        EasingFunction::CubicIn => p.powi(3),
+        EasingFunction::CubicOut => 1.0 - (1.0 - p).powi(3),
+        EasingFunction::CubicInOut => in_out(p, 3),
+
+        EasingFunction::QuarticIn => p.powi(4),
+        EasingFunction::QuarticOut => 1.0 - (1.0 - p).powi(4),
+        EasingFunction::QuarticInOut => in_out(p, 4),
+
+        EasingFunction::QuinticIn => p.powi(5),
+        EasingFunction::QuinticOut => 1.0 - (1.0 - p).powi(5),
+        EasingFunction::QuinticInOut => in_out(p, 5),
+
+        EasingFunction::ExponentialIn => {
+            if p == 0.0 {
+                0.0
+            } else {
+                f32::powf(2.0, 10.0 * p - 10.0)
+            }
+        }
+        EasingFunction::ExponentialOut => {
+            if p == 1.0 {
+                1.0
+            } else {
+                1.0 - f32::powf(2.0, -10.0 * p)
+            }
+        }
+        EasingFunction::ExponentialInOut => {
+            if p == 0.0 || p == 1.0 {
+                p
+            } else if p < 0.5 {
+                f32::powf(2.0, 20.0 * p - 10.0) / 2.0
+            } else {
+                (2.0 - f32::powf(2.0, -20.0 * p + 10.0)) / 2.0
+            }
+        }
+
+        EasingFunction::CircularIn => 1.0 - (1.0 - p * p).sqrt(),
+        EasingFunction::CircularOut => (1.0 - (p - 1.0) * (p - 1.0)).sqrt(),
+        EasingFunction::CircularInOut => {
+            if p < 0.5 {
+                (1.0 - (1.0 - (2.0 * p).powi(2)).sqrt()) / 2.0
+            } else {
+                ((1.0 - (-2.0 * p + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+            }
+        }
+
+        EasingFunction::BackIn => BACK_C3 * p * p * p - BACK_C1 * p * p,
+        EasingFunction::BackOut => 1.0 + BACK_C3 * (p - 1.0).powi(3) + BACK_C1 * (p - 1.0).powi(2),
+        EasingFunction::BackInOut => {
+            if p < 0.5 {
+                ((2.0 * p).powi(2) * ((BACK_C2 + 1.0) * 2.0 * p - BACK_C2)) / 2.0
+            } else {
+                ((2.0 * p - 2.0).powi(2) * ((BACK_C2 + 1.0) * (p * 2.0 - 2.0) + BACK_C2) + 2.0)
+                    / 2.0
+            }
+        }
+
+        EasingFunction::ElasticIn => {
+            if p == 0.0 || p == 1.0 {
+                p
+            } else {
+                -f32::powf(2.0, 10.0 * p - 10.0) * ((p * 10.0 - 10.75) * ELASTIC_C4).sin()
+            }
+        }
+        EasingFunction::ElasticOut => {
+            if p == 0.0 || p == 1.0 {
+                p
+            } else {
+                f32::powf(2.0, -10.0 * p) * ((p * 10.0 - 0.75) * ELASTIC_C4).sin() + 1.0
+            }
+        }
+        EasingFunction::ElasticInOut => {
+            if p == 0.0 || p == 1.0 {
+                p
+            } else if p < 0.5 {
+                -(f32::powf(2.0, 20.0 * p - 10.0) * ((20.0 * p - 11.125) * ELASTIC_C5).sin()) / 2.0
+            } else {
+                (f32::powf(2.0, -20.0 * p + 10.0) * ((20.0 * p - 11.125) * ELASTIC_C5).sin()) / 2.0
+                    + 1.0
+            }
+        }
+
+        EasingFunction::BounceIn => 1.0 - bounce_out(1.0 - p),
+        EasingFunction::BounceOut => bounce_out(p),
+        EasingFunction::BounceInOut => {
+            if p < 0.5 {
+                (1.0 - bounce_out(1.0 - 2.0 * p)) / 2.0
             } else {
-                return (54.0 / 5.0) * p * p - (513.0 / 25.0) * p + 268.0 / 25.0;
+                (1.0 + bounce_out(2.0 * p - 1.0)) / 2.0
             }
         }
+
+        EasingFunction::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, p),
+        EasingFunction::Steps(steps, position) => steps_ratio(steps, position, p),
+    }
+}
+
+// symmetric in/out for the polynomial family (quadratic..quintic)
+fn in_out(p: f32, power: i32) -> f32 {
+    if p < 0.5 {
+        f32::powi(2.0, power - 1) * p.powi(power)
+    } else {
+        1.0 - (-2.0 * p + 2.0).powi(power) / 2.0
+    }
+}
+
+fn bounce_out(p: f32) -> f32 {
+    if p < 4.0 / 11.0 {
+        (121.0 * p * p) / 16.0
+    } else if p < 8.0 / 11.0 {
+        (363.0 / 40.0) * p * p - (99.0 / 10.0) * p + 17.0 / 5.0
+    } else if p < 9.0 / 10.0 {
+        (4356.0 / 361.0) * p * p - (35442.0 / 1805.0) * p + 16061.0 / 1805.0
+    } else {
+        (54.0 / 5.0) * p * p - (513.0 / 25.0) * p + 268.0 / 25.0
+    }
+}
+
+// same approach as the browsers: a few newton iterations on x(s) = p,
+// falling back to bisection when the slope is too flat
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, p: f32) -> f32 {
+    if p == 0.0 || p == 1.0 {
+        return p;
+    }
+
+    // b(s) = 3(1-s)^2 s c1 + 3(1-s) s^2 c2 + s^3 , written in polynomial form
+    let bezier = |s: f32, c1: f32, c2: f32| {
+        let c = 3.0 * c1;
+        let b = 3.0 * (c2 - c1) - c;
+        let a = 1.0 - c - b;
+        ((a * s + b) * s + c) * s
+    };
+    let bezier_slope = |s: f32, c1: f32, c2: f32| {
+        let c = 3.0 * c1;
+        let b = 3.0 * (c2 - c1) - c;
+        let a = 1.0 - c - b;
+        (3.0 * a * s + 2.0 * b) * s + c
+    };
+
+    let x1 = f32::clamp(x1, 0.0, 1.0);
+    let x2 = f32::clamp(x2, 0.0, 1.0);
+
+    let mut s = p;
+    for _ in 0..8 {
+        let err = bezier(s, x1, x2) - p;
+        if err.abs() < 1e-6 {
+            return bezier(s, y1, y2);
+        }
+        let slope = bezier_slope(s, x1, x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s -= err / slope;
+    }
+
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    s = p;
+    for _ in 0..32 {
+        let x = bezier(s, x1, x2);
+        if (x - p).abs() < 1e-6 {
+            break;
+        }
+        if x < p {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
     }
+    bezier(s, y1, y2)
+}
+
+// https://www.w3.org/TR/css-easing-1/#step-easing-algo
+fn steps_ratio(steps: u32, position: StepPosition, p: f32) -> f32 {
+    let steps = steps.max(1);
+    let jumps = match position {
+        StepPosition::JumpStart | StepPosition::JumpEnd => steps,
+        StepPosition::JumpNone => (steps - 1).max(1),
+        StepPosition::JumpBoth => steps + 1,
+    } as f32;
+
+    let mut current = (p * steps as f32).floor();
+    if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+        current += 1.0;
+    }
+
+    current.min(jumps) / jumps
 }
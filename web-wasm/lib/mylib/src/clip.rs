@@ -0,0 +1,380 @@
+// animations as data: a `Clip` is what `AnimateComponent<Transform>` is built
+// from, loaded from JSON or RON. in RON a clip looks like
+//
+// (
+//     name: "bounce",
+//     sequences: [(
+//         steps: [
+//             Tween((duration: 500, ease: QuadraticOut, target: PositionY(from: 0, to: 2))),
+//             Delay(100),
+//             Call((name: "footstep_sys", tag: 1)),
+//             Par([(offset: 0, step: Tween(...)), (offset: 250, step: Seq(...))]),
+//         ],
+//         repeat: Infinite,
+//         yoyo: true,
+//     )],
+// )
+//
+// and the same in JSON, enums being `{ "Variant": ... }`. optional fields
+// left at their default are not written back, so clips round-trip.
+// any reflected field of the transform can be reached with
+// `Path(path: "Transform.position.y", from: 0, to: 2)`, paths are checked
+// when the clip is loaded. a tween with `relative: Some(By)` and no `from`
+// starts wherever the entity is, see `RelativeLens`: only the built-in
+// targets can be relative. `Call` runs a JS system, see `Call::js`
+
+use std::collections::HashMap;
+use std::fmt;
+
+use bevy_ecs::system::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::action::Call;
+use crate::animation::{AnimateComponent, EasingFunction, Par, Repeat, Seq, Tween};
+use crate::lens::{Lens, Relative, RelativeLens};
+use crate::property::{PathError, PropertyLens};
+use crate::{Color, Quat, Transform, Vector3};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Clip {
+    pub name: String,
+    pub sequences: Vec<SeqDef>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SeqDef {
+    pub steps: Vec<StepDef>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub repeat: Repeat,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub yoyo: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub repeat_delay: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum StepDef {
+    Tween(TweenDef),
+    Delay(f32),
+    Seq(SeqDef),
+    Par(Vec<ParChildDef>),
+    Call(CallDef),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TweenDef {
+    pub duration: f32,
+    pub ease: EasingFunction,
+    pub target: TransformProperty,
+    // see `Tween::with_tag`
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tag: u32,
+    // `to` is read as `RelativeLens::value` and `from` is ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative: Option<Relative>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CallDef {
+    // of the JS system
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tag: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParChildDef {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub offset: f32,
+    pub step: StepDef,
+}
+
+// the `Transform` lenses a clip can use, `from` is required unless the
+// tween is relative
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum TransformProperty {
+    Position {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<Vector3>,
+        to: Vector3,
+    },
+    PositionX {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<f32>,
+        to: f32,
+    },
+    PositionY {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<f32>,
+        to: f32,
+    },
+    PositionZ {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<f32>,
+        to: f32,
+    },
+    Rotation {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<Quat>,
+        to: Quat,
+    },
+    Scale {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<Vector3>,
+        to: Vector3,
+    },
+    // see `PropertyLens`
+    Path {
+        path: String,
+        from: PathValue,
+        to: PathValue,
+    },
+}
+
+// what a `Path` tweens, told apart by shape: a number or the fields of the
+// struct. quaternions come before vectors, an `x, y, z, w` object would
+// read as a vector otherwise
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum PathValue {
+    F32(f32),
+    Quat(Quat),
+    Vector3(Vector3),
+    Color(Color),
+}
+
+fn is_default<V: Default + PartialEq>(v: &V) -> bool {
+    *v == V::default()
+}
+
+// `from: 0` rather than `from: Some(0)`
+fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+}
+
+fn required<V>(from: Option<V>) -> Result<V, ClipError> {
+    from.ok_or(ClipError::MissingFrom)
+}
+
+#[derive(Debug)]
+pub enum ClipError {
+    Json(serde_json::Error),
+    Ron(ron::error::SpannedError),
+    RonOut(ron::Error),
+    Path(PathError),
+    // `from` and `to` of a `Path` are not the same kind of value
+    MismatchedValues { path: String },
+    // an absolute tween without `from`
+    MissingFrom,
+    // `Path` targets can only be absolute
+    RelativePath { path: String },
+}
+
+impl From<PathError> for ClipError {
+    fn from(err: PathError) -> Self {
+        ClipError::Path(err)
+    }
+}
+
+impl fmt::Display for ClipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipError::Json(err) => write!(f, "invalid clip json: {}", err),
+            ClipError::Ron(err) => write!(f, "invalid clip ron: {}", err),
+            ClipError::RonOut(err) => write!(f, "could not write clip ron: {}", err),
+            ClipError::Path(err) => write!(f, "invalid clip target: {}", err),
+            ClipError::MismatchedValues { path } => {
+                write!(
+                    f,
+                    "invalid clip target: `{}` goes between two kinds of values",
+                    path
+                )
+            }
+            ClipError::MissingFrom => {
+                write!(
+                    f,
+                    "invalid clip target: a tween that isn't relative needs a `from`"
+                )
+            }
+            ClipError::RelativePath { path } => {
+                write!(
+                    f,
+                    "invalid clip target: `{}` can't be tweened relatively",
+                    path
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClipError {}
+
+impl Clip {
+    pub fn from_json(json: &str) -> Result<Clip, ClipError> {
+        let clip: Clip = serde_json::from_str(json).map_err(ClipError::Json)?;
+        clip.build()?;
+        Ok(clip)
+    }
+
+    pub fn to_json(&self) -> Result<String, ClipError> {
+        serde_json::to_string_pretty(self).map_err(ClipError::Json)
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Clip, ClipError> {
+        let clip: Clip = ron_options().from_str(ron).map_err(ClipError::Ron)?;
+        clip.build()?;
+        Ok(clip)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ClipError> {
+        ron_options()
+            .to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(ClipError::RonOut)
+    }
+
+    // only fails on targets the loaders already checked
+    pub fn build(&self) -> Result<AnimateComponent<Transform>, ClipError> {
+        let mut animate = AnimateComponent::new();
+        for seq in &self.sequences {
+            animate.add_seq(seq.build()?);
+        }
+        Ok(animate)
+    }
+}
+
+impl SeqDef {
+    pub fn build(&self) -> Result<Seq<Transform>, ClipError> {
+        let mut seq = Seq::new();
+        for step in &self.steps {
+            match step {
+                StepDef::Tween(tween) => seq.then(tween.build()?),
+                StepDef::Delay(duration) => seq.then_delay(*duration),
+                StepDef::Seq(child) => seq.then(child.build()?),
+                StepDef::Par(children) => seq.then(build_par(children)?),
+                StepDef::Call(call) => seq.then(Call::js(&call.name).with_tag(call.tag)),
+            };
+        }
+        seq.repeat(self.repeat)
+            .yoyo(self.yoyo)
+            .repeat_delay(self.repeat_delay);
+        Ok(seq)
+    }
+}
+
+// a single step is wrapped in a sequence, `Par` children all have the same type
+fn build_par(children: &[ParChildDef]) -> Result<Par<Transform>, ClipError> {
+    let mut par = Par::new();
+    for child in children {
+        let seq = SeqDef {
+            steps: vec![child.step.clone()],
+            ..SeqDef::default()
+        };
+        par.add_at(child.offset, seq.build()?);
+    }
+    Ok(par)
+}
+
+impl TweenDef {
+    pub fn build(&self) -> Result<Tween<Transform>, ClipError> {
+        if let Some(relative) = self.relative {
+            return Ok(self.build_relative(relative)?.with_tag(self.tag));
+        }
+        let (ease, duration) = (self.ease, self.duration);
+        let tween = match self.target.clone() {
+            TransformProperty::Position { from, to } => {
+                Tween::new(ease, duration, Lens::position(required(from)?, to))
+            }
+            TransformProperty::PositionX { from, to } => {
+                Tween::new(ease, duration, Lens::position_x(required(from)?, to))
+            }
+            TransformProperty::PositionY { from, to } => {
+                Tween::new(ease, duration, Lens::position_y(required(from)?, to))
+            }
+            TransformProperty::PositionZ { from, to } => {
+                Tween::new(ease, duration, Lens::position_z(required(from)?, to))
+            }
+            TransformProperty::Rotation { from, to } => {
+                Tween::new(ease, duration, Lens::rotation(required(from)?, to))
+            }
+            TransformProperty::Scale { from, to } => {
+                Tween::new(ease, duration, Lens::scale(required(from)?, to))
+            }
+            TransformProperty::Path { path, from, to } => self.build_path(&path, from, to)?,
+        };
+        Ok(tween.with_tag(self.tag))
+    }
+
+    fn build_relative(&self, relative: Relative) -> Result<Tween<Transform>, ClipError> {
+        let (ease, duration) = (self.ease, self.duration);
+        Ok(match self.target.clone() {
+            TransformProperty::Position { to, .. } => {
+                Tween::new(ease, duration, RelativeLens::position(relative, to))
+            }
+            TransformProperty::PositionX { to, .. } => {
+                Tween::new(ease, duration, RelativeLens::position_x(relative, to))
+            }
+            TransformProperty::PositionY { to, .. } => {
+                Tween::new(ease, duration, RelativeLens::position_y(relative, to))
+            }
+            TransformProperty::PositionZ { to, .. } => {
+                Tween::new(ease, duration, RelativeLens::position_z(relative, to))
+            }
+            TransformProperty::Rotation { to, .. } => {
+                Tween::new(ease, duration, RelativeLens::rotation(relative, to))
+            }
+            TransformProperty::Scale { to, .. } => {
+                Tween::new(ease, duration, RelativeLens::scale(relative, to))
+            }
+            TransformProperty::Path { path, .. } => return Err(ClipError::RelativePath { path }),
+        })
+    }
+
+    fn build_path(
+        &self,
+        path: &str,
+        from: PathValue,
+        to: PathValue,
+    ) -> Result<Tween<Transform>, ClipError> {
+        let (ease, duration) = (self.ease, self.duration);
+        Ok(match (from, to) {
+            (PathValue::F32(from), PathValue::F32(to)) => Tween::new(
+                ease,
+                duration,
+                PropertyLens::<Transform, _>::new(path, from, to)?,
+            ),
+            (PathValue::Quat(from), PathValue::Quat(to)) => Tween::new(
+                ease,
+                duration,
+                PropertyLens::<Transform, _>::new(path, from, to)?,
+            ),
+            (PathValue::Vector3(from), PathValue::Vector3(to)) => Tween::new(
+                ease,
+                duration,
+                PropertyLens::<Transform, _>::new(path, from, to)?,
+            ),
+            (PathValue::Color(from), PathValue::Color(to)) => Tween::new(
+                ease,
+                duration,
+                PropertyLens::<Transform, _>::new(path, from, to)?,
+            ),
+            _ => return Err(ClipError::MismatchedValues { path: path.into() }),
+        })
+    }
+}
+
+// clips by name, filled by `load_clip`
+#[derive(Resource, Default, Debug)]
+pub struct ClipLibrary {
+    clips: HashMap<String, Clip>,
+}
+
+impl ClipLibrary {
+    // replaces any clip with the same name
+    pub fn insert(&mut self, clip: Clip) {
+        self.clips.insert(clip.name.clone(), clip);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Clip> {
+        self.clips.get(name)
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt::Debug;
+
+use crate::animation::{EasingFunction, Tweenable};
+use crate::lens::Lerp;
+use crate::{Quat, Transform, Vector3};
+
+// samples per segment of the arc length table
+const SAMPLES: usize = 32;
+
+// a path through space made of segments, each parameterized from 0 to 1
+#[derive(Debug, Clone, PartialEq)]
+pub enum Curve {
+    // straight lines between the points
+    Polyline(Vec<Vector3>),
+    // from `start`, each segment is `[control, end]`
+    Quadratic {
+        start: Vector3,
+        segments: Vec<[Vector3; 2]>,
+    },
+    // from `start`, each segment is `[control1, control2, end]`
+    Cubic {
+        start: Vector3,
+        segments: Vec<[Vector3; 3]>,
+    },
+    // uniform catmull-rom through every point, the ends are repeated to get
+    // the tangents of the first and last segments
+    CatmullRom(Vec<Vector3>),
+}
+
+impl Curve {
+    pub fn segment_count(&self) -> usize {
+        match self {
+            Curve::Polyline(points) | Curve::CatmullRom(points) => points.len().saturating_sub(1),
+            Curve::Quadratic { segments, .. } => segments.len(),
+            Curve::Cubic { segments, .. } => segments.len(),
+        }
+    }
+
+    // point and derivative of `segment` at `t` in [0, 1]
+    fn eval(&self, segment: usize, t: f32) -> (Vector3, Vector3) {
+        let u = 1.0 - t;
+        match self {
+            Curve::Polyline(points) => {
+                let (a, b) = (points[segment], points[segment + 1]);
+                (a.lerp(b, t), b - a)
+            }
+            Curve::Quadratic { start, segments } => {
+                let a = if segment == 0 {
+                    *start
+                } else {
+                    segments[segment - 1][1]
+                };
+                let [c, b] = segments[segment];
+                (
+                    a * (u * u) + c * (2.0 * u * t) + b * (t * t),
+                    (c - a) * (2.0 * u) + (b - c) * (2.0 * t),
+                )
+            }
+            Curve::Cubic { start, segments } => {
+                let a = if segment == 0 {
+                    *start
+                } else {
+                    segments[segment - 1][2]
+                };
+                let [c1, c2, b] = segments[segment];
+                (
+                    a * (u * u * u)
+                        + c1 * (3.0 * u * u * t)
+                        + c2 * (3.0 * u * t * t)
+                        + b * (t * t * t),
+                    (c1 - a) * (3.0 * u * u) + (c2 - c1) * (6.0 * u * t) + (b - c2) * (3.0 * t * t),
+                )
+            }
+            Curve::CatmullRom(points) => {
+                let last = points.len() - 1;
+                let p0 = points[segment.saturating_sub(1)];
+                let (p1, p2) = (points[segment], points[segment + 1]);
+                let p3 = points[(segment + 2).min(last)];
+                let (t2, t3) = (t * t, t * t * t);
+                let point = (p1 * 2.0
+                    + (p2 - p0) * t
+                    + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+                    + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+                    * 0.5;
+                let derivative = ((p2 - p0)
+                    + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * (2.0 * t)
+                    + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * (3.0 * t2))
+                    * 0.5;
+                (point, derivative)
+            }
+        }
+    }
+
+    fn first(&self) -> Vector3 {
+        match self {
+            Curve::Polyline(points) | Curve::CatmullRom(points) => {
+                points.first().copied().unwrap_or(Vector3::ZERO)
+            }
+            Curve::Quadratic { start, .. } | Curve::Cubic { start, .. } => *start,
+        }
+    }
+
+    // point and derivative at `u` from 0 to `segment_count`
+    pub fn at(&self, u: f32) -> (Vector3, Vector3) {
+        let count = self.segment_count();
+        if count == 0 {
+            return (self.first(), Vector3::ZERO);
+        }
+        let u = u.clamp(0.0, count as f32);
+        let segment = (u as usize).min(count - 1);
+        self.eval(segment, u - segment as f32)
+    }
+}
+
+// the curve with a table of its length at evenly spaced parameters, to move
+// along it at constant speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthCurve {
+    curve: Curve,
+    // SAMPLES per segment, starts at 0
+    lengths: Vec<f32>,
+}
+
+impl ArcLengthCurve {
+    pub fn new(curve: Curve) -> Self {
+        let samples = curve.segment_count() * SAMPLES;
+        let mut lengths = Vec::with_capacity(samples + 1);
+        lengths.push(0.0);
+        let mut previous = curve.first();
+        for i in 1..=samples {
+            let point = curve.at(i as f32 / SAMPLES as f32).0;
+            lengths.push(lengths[i - 1] + (point - previous).length());
+            previous = point;
+        }
+        Self { curve, lengths }
+    }
+
+    pub fn curve(&self) -> &Curve {
+        &self.curve
+    }
+
+    pub fn length(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    // the curve parameter `distance` along it
+    pub fn parameter(&self, distance: f32) -> f32 {
+        if self.lengths.len() < 2 {
+            return 0.0;
+        }
+        let last = self.lengths.len() - 2;
+        let index = (self.lengths.partition_point(|&l| l <= distance) - 1).min(last);
+        let (from, to) = (self.lengths[index], self.lengths[index + 1]);
+        let step = if to > from {
+            (distance - from) / (to - from)
+        } else {
+            0.0
+        };
+        (index as f32 + step.clamp(0.0, 1.0)) / SAMPLES as f32
+    }
+
+    // point and unit tangent `ratio` of the way along the curve, by length
+    // or by parameter. past the ends it goes on along the end tangents, for
+    // easings that overshoot
+    pub fn at(&self, ratio: f32, arc_length: bool) -> (Vector3, Vector3) {
+        let count = self.curve.segment_count() as f32;
+        let clamped = ratio.clamp(0.0, 1.0);
+        let u = if arc_length {
+            self.parameter(clamped * self.length())
+        } else {
+            clamped * count
+        };
+        let (point, derivative) = self.curve.at(u);
+        let tangent = derivative.normalize();
+        (
+            point + tangent * ((ratio - clamped) * self.length()),
+            tangent,
+        )
+    }
+}
+
+impl From<Curve> for ArcLengthCurve {
+    fn from(curve: Curve) -> Self {
+        Self::new(curve)
+    }
+}
+
+// writes the rotation taking `forward` to the path's tangent
+struct Orient<T> {
+    get: fn(&mut T) -> &mut Quat,
+    forward: Vector3,
+}
+
+impl<T> Clone for Orient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            get: self.get,
+            forward: self.forward,
+        }
+    }
+}
+
+// moves the `Vector3` that `get` points at along a curve, eased like any
+// tween. by default the ratio is a fraction of the length so the speed is
+// constant, however the control points are spread
+pub struct PathTween<T> {
+    // absolute
+    pub start_abs: f32,
+    pub ease: EasingFunction,
+    pub duration: f32,
+    // false moves by curve parameter, faster where the points are far apart
+    pub arc_length: bool,
+    pub curve: ArcLengthCurve,
+    get: fn(&mut T) -> &mut Vector3,
+    orient: Option<Orient<T>>,
+}
+
+impl<T> PathTween<T> {
+    pub fn new(
+        ease: EasingFunction,
+        duration: f32,
+        curve: impl Into<ArcLengthCurve>,
+        get: fn(&mut T) -> &mut Vector3,
+    ) -> Self {
+        Self {
+            start_abs: 0.0,
+            ease,
+            duration,
+            arc_length: true,
+            curve: curve.into(),
+            get,
+            orient: None,
+        }
+    }
+
+    // also turns the `Quat` that `get` points at so `forward` follows the
+    // tangent, by the shortest arc: there is no up vector, the roll is
+    // whatever that gives
+    pub fn oriented(mut self, get: fn(&mut T) -> &mut Quat, forward: Vector3) -> Self {
+        self.orient = Some(Orient {
+            get,
+            forward: forward.normalize(),
+        });
+        self
+    }
+}
+
+impl PathTween<Transform> {
+    pub fn position(ease: EasingFunction, duration: f32, curve: impl Into<ArcLengthCurve>) -> Self {
+        Self::new(ease, duration, curve, |t| &mut t.position)
+    }
+
+    // the transform's rotation faces along the path
+    pub fn facing(self, forward: Vector3) -> Self {
+        self.oriented(|t| &mut t.rotation, forward)
+    }
+}
+
+impl<T: 'static> Tweenable<T> for PathTween<T> {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        self.start_abs = v;
+    }
+
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        let (point, tangent) = self.curve.at(ratio, self.arc_length);
+        *(self.get)(comp) = point;
+        if let Some(orient) = &self.orient {
+            // a degenerate path has no direction, keep the rotation
+            if tangent != Vector3::ZERO {
+                *(orient.get)(comp) = Quat::from_rotation_arc(orient.forward, tangent);
+            }
+        }
+    }
+
+    fn ease(&self) -> EasingFunction {
+        self.ease
+    }
+}
+
+impl<T> Clone for PathTween<T> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            ease: self.ease,
+            duration: self.duration,
+            arc_length: self.arc_length,
+            curve: self.curve.clone(),
+            get: self.get,
+            orient: self.orient.clone(),
+        }
+    }
+}
+
+impl<T> Debug for PathTween<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathTween")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("ease", &self.ease)
+            .field("duration", &self.duration)
+            .field("arc_length", &self.arc_length)
+            .field("curve", &self.curve.curve)
+            .field("oriented", &self.orient.as_ref().map(|o| o.forward))
+            .finish()
+    }
+}
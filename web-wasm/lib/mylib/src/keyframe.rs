@@ -0,0 +1,197 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::animation::{field_bytes, EasingFunction, Tweenable};
+use crate::lens::Animatable;
+
+// how a key gets to the next one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    // holds the value until the next key
+    Step,
+    Linear,
+    // cubic hermite with the keys' own tangents (per unit of time, like glTF)
+    Hermite,
+    // cubic hermite with tangents from the neighbouring keys
+    CatmullRom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe<V> {
+    // relative to the start of the track
+    pub time: f32,
+    pub value: V,
+    // only read by `Hermite` segments
+    pub in_tangent: V,
+    pub out_tangent: V,
+    // of the segment that starts at this key
+    pub interpolation: Interpolation,
+}
+
+// a curve through timestamped keys written to the `V` that `get` points at,
+// the value holds on the first key before it and on the last one after
+pub struct KeyframeTrack<T, V> {
+    // absolute
+    pub start_abs: f32,
+    // sorted by time
+    keys: Vec<Keyframe<V>>,
+    get: fn(&mut T) -> &mut V,
+}
+
+impl<T, V: Animatable> KeyframeTrack<T, V> {
+    pub fn new(get: fn(&mut T) -> &mut V) -> Self {
+        Self {
+            start_abs: 0.0,
+            keys: Vec::new(),
+            get,
+        }
+    }
+
+    pub fn keys(&self) -> &[Keyframe<V>] {
+        &self.keys
+    }
+
+    // keeps the keys sorted, a key at the same time as another goes after it
+    pub fn add_key(&mut self, key: Keyframe<V>) -> &mut Self {
+        let index = self.keys.partition_point(|k| k.time <= key.time);
+        self.keys.insert(index, key);
+        self
+    }
+
+    pub fn key(&mut self, time: f32, value: V, interpolation: Interpolation) -> &mut Self {
+        let zero = value * 0.0;
+        self.add_key(Keyframe {
+            time,
+            value,
+            in_tangent: zero,
+            out_tangent: zero,
+            interpolation,
+        })
+    }
+
+    pub fn hermite_key(&mut self, time: f32, value: V, in_tangent: V, out_tangent: V) -> &mut Self {
+        self.add_key(Keyframe {
+            time,
+            value,
+            in_tangent,
+            out_tangent,
+            interpolation: Interpolation::Hermite,
+        })
+    }
+
+    // value at `time` from the start of the track, none without keys
+    pub fn value_at(&self, time: f32) -> Option<V> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+        if time >= last.time {
+            return Some(last.value);
+        }
+
+        // a.time <= time < b.time, so the segment can't be empty
+        let index = self.keys.partition_point(|k| k.time <= time) - 1;
+        let (a, b) = (&self.keys[index], &self.keys[index + 1]);
+        let dt = b.time - a.time;
+        let t = (time - a.time) / dt;
+
+        Some(match a.interpolation {
+            Interpolation::Step => a.value,
+            Interpolation::Linear => a.value.lerp(b.value, t),
+            Interpolation::Hermite => {
+                hermite(a.value, a.out_tangent * dt, b.value, b.in_tangent * dt, t)
+            }
+            Interpolation::CatmullRom => hermite(
+                a.value,
+                self.catmull_rom_tangent(index) * dt,
+                b.value,
+                self.catmull_rom_tangent(index + 1) * dt,
+                t,
+            ),
+        })
+    }
+
+    // slope between the neighbours of key `index`, one sided at both ends
+    fn catmull_rom_tangent(&self, index: usize) -> V {
+        let prev = &self.keys[index.saturating_sub(1)];
+        let next = &self.keys[usize::min(index + 1, self.keys.len() - 1)];
+        let dt = next.time - prev.time;
+        if dt <= 0.0 {
+            return prev.value * 0.0;
+        }
+        (next.value - prev.value) * (1.0 / dt)
+    }
+}
+
+fn hermite<V: Animatable>(p0: V, m0: V, p1: V, m1: V, t: f32) -> V {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+        + m0 * (t3 - 2.0 * t2 + t)
+        + p1 * (-2.0 * t3 + 3.0 * t2)
+        + m1 * (t3 - t2)
+}
+
+impl<T, V> Tweenable<T> for KeyframeTrack<T, V>
+where
+    T: 'static,
+    V: Animatable + Debug + Send + Sync + 'static,
+{
+    // up to the last key, the time before the first one included
+    fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |key| key.time.max(0.0))
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        self.start_abs = v;
+    }
+
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        let time = self.start_abs + ratio * self.duration();
+        self.sample(comp, time);
+    }
+
+    // the keys do the shaping
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        if let Some(value) = self.value_at(time - self.start_abs) {
+            *(self.get)(comp) = value;
+        }
+    }
+
+    // a track without keys writes nothing
+    fn writes(&self, comp: &mut T) -> Option<Range<usize>> {
+        match self.keys.is_empty() {
+            true => Some(0..0),
+            false => field_bytes(comp, |comp| Some((self.get)(comp))),
+        }
+    }
+}
+
+impl<T, V: Clone> Clone for KeyframeTrack<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            keys: self.keys.clone(),
+            get: self.get,
+        }
+    }
+}
+
+impl<T, V: Debug> Debug for KeyframeTrack<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyframeTrack")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
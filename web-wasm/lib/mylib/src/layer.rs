@@ -0,0 +1,206 @@
+use std::fmt::Debug;
+
+use crate::animation::{Seq, Tweenable};
+use crate::lens::{Lerp, Offset};
+use crate::Transform;
+
+// how a layer's pose combines with the layers under it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    // goes `weight` of the way from what's under it to the layer's pose
+    Override,
+    // adds `weight` times the layer's change from the rest pose
+    Additive,
+}
+
+// components whose animations can be layered, field by field
+pub trait Blend {
+    fn blend(&mut self, to: &Self, weight: f32);
+    fn add(&mut self, rest: &Self, pose: &Self, weight: f32);
+    // takes the fields of `now` that differ from `written`, what something
+    // other than the blend changed since it wrote `written`
+    fn adopt(&mut self, written: &Self, now: &Self);
+}
+
+impl<V: Offset + PartialEq> Blend for V {
+    fn blend(&mut self, to: &Self, weight: f32) {
+        *self = self.lerp(*to, weight);
+    }
+
+    fn add(&mut self, rest: &Self, pose: &Self, weight: f32) {
+        let change = rest.inverse().offset(*pose);
+        *self = self.offset(V::zero().lerp(change, weight));
+    }
+
+    fn adopt(&mut self, written: &Self, now: &Self) {
+        if now != written {
+            *self = *now;
+        }
+    }
+}
+
+impl Blend for Transform {
+    fn blend(&mut self, to: &Self, weight: f32) {
+        self.position.blend(&to.position, weight);
+        self.rotation.blend(&to.rotation, weight);
+        self.scale.blend(&to.scale, weight);
+    }
+
+    fn add(&mut self, rest: &Self, pose: &Self, weight: f32) {
+        self.position.add(&rest.position, &pose.position, weight);
+        self.rotation.add(&rest.rotation, &pose.rotation, weight);
+        self.scale.add(&rest.scale, &pose.scale, weight);
+    }
+
+    fn adopt(&mut self, written: &Self, now: &Self) {
+        self.position.adopt(&written.position, &now.position);
+        self.rotation.adopt(&written.rotation, &now.rotation);
+        self.scale.adopt(&written.scale, &now.scale);
+    }
+}
+
+// what `AnimateComponent` keeps of `Blend`, so only components that get
+// layers have to implement it
+pub(crate) type BlendFn<T> = fn(&mut T, &T, &T, BlendMode, f32);
+pub(crate) type AdoptFn<T> = fn(&mut T, &T, &T);
+
+pub(crate) fn blend<T: Blend>(out: &mut T, rest: &T, pose: &T, mode: BlendMode, weight: f32) {
+    match mode {
+        BlendMode::Override => out.blend(pose, weight),
+        BlendMode::Additive => out.add(rest, pose, weight),
+    }
+}
+
+pub(crate) fn adopt<T: Blend>(pose: &mut T, written: &T, now: &T) {
+    pose.adopt(written, now)
+}
+
+// a weight change over time, see `AnimationLayer::fade_to`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Fade {
+    from: f32,
+    duration: f32,
+    // set on the first frame the fade is evaluated
+    start: Option<f32>,
+}
+
+// sequences evaluated on a pose of their own that starts at the rest pose
+// (the entity's snapshot), then blended over the layers before it. a field
+// the layer doesn't animate stays at rest, so a partial override layer
+// pulls the other fields back: use `Additive` for those. a field something
+// else writes between two frames becomes part of the rest pose and of every
+// pose, it stays as written until a layer animates it again
+pub struct AnimationLayer<T> {
+    pub sequences: Vec<Seq<T>>,
+    pub mode: BlendMode,
+    // where the weight ends up when fading
+    pub weight: f32,
+    // absolute time the sequences start at, 0 unless set. none starts them
+    // on the first frame the layer plays, which is what `crossfade` does
+    pub start: Option<f32>,
+    fade: Option<Fade>,
+    // weight of the last frame
+    current_weight: f32,
+    pub(crate) pose: Option<T>,
+}
+
+impl<T: 'static> AnimationLayer<T> {
+    pub fn new(mode: BlendMode, weight: f32) -> Self {
+        Self {
+            sequences: Vec::new(),
+            mode,
+            weight,
+            start: Some(0.0),
+            fade: None,
+            current_weight: weight,
+            pose: None,
+        }
+    }
+
+    pub fn add_seq(&mut self, seq: Seq<T>) -> &mut Self {
+        self.sequences.push(seq);
+        self
+    }
+
+    // goes from the current weight to `weight` in `duration` ms, starting
+    // on the next frame
+    pub fn fade_to(&mut self, weight: f32, duration: f32) -> &mut Self {
+        self.fade = Some(Fade {
+            from: self.current_weight,
+            duration,
+            start: None,
+        });
+        self.weight = weight;
+        self
+    }
+
+    // from nothing, see `AnimateComponent::crossfade`
+    pub(crate) fn fade_in(&mut self, duration: f32) -> &mut Self {
+        self.current_weight = 0.0;
+        self.fade_to(self.weight, duration)
+    }
+
+    pub fn current_weight(&self) -> f32 {
+        self.current_weight
+    }
+
+    pub(crate) fn weight_at(&mut self, time: f32) -> f32 {
+        self.current_weight = match &mut self.fade {
+            Some(fade) => {
+                let start = *fade.start.get_or_insert(time);
+                let ratio = if fade.duration > 0.0 {
+                    ((time - start) / fade.duration).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+                fade.from.lerp(self.weight, ratio)
+            }
+            None => self.weight,
+        };
+        self.current_weight
+    }
+
+    // an override at full weight, nothing under it shows
+    pub(crate) fn covers(&self) -> bool {
+        self.mode == BlendMode::Override && self.weight >= 1.0 && self.current_weight >= 1.0
+    }
+
+    // end of the longest sequence or of the fade, in absolute time. a layer
+    // that hasn't started yet starts at `now`
+    pub(crate) fn end(&self, now: f32) -> f32 {
+        let start = self.start.unwrap_or(now);
+        let fade = self
+            .fade
+            .map_or(0.0, |fade| fade.start.unwrap_or(start) + fade.duration);
+        self.sequences
+            .iter()
+            .map(|seq| start + seq.start_abs() + seq.duration())
+            .fold(fade, f32::max)
+    }
+}
+
+impl<T: Clone> Clone for AnimationLayer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sequences: self.sequences.clone(),
+            mode: self.mode,
+            weight: self.weight,
+            start: self.start,
+            fade: self.fade,
+            current_weight: self.current_weight,
+            pose: self.pose.clone(),
+        }
+    }
+}
+
+impl<T: Debug + 'static> Debug for AnimationLayer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnimationLayer")
+            .field("sequences", &self.sequences)
+            .field("mode", &self.mode)
+            .field("weight", &self.weight)
+            .field("start", &self.start)
+            .field("current_weight", &self.current_weight)
+            .finish()
+    }
+}
@@ -0,0 +1,387 @@
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Range, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use crate::animation::{field_bytes, TweenTarget};
+use crate::{Color, Quat, Transform, Vector3};
+
+// values a tween can go through, `ratio` is the eased one so it can leave
+// [0, 1] (back, elastic...)
+pub trait Lerp: Copy {
+    fn lerp(self, to: Self, ratio: f32) -> Self;
+}
+
+// values that also have the arithmetic cubic curves need. quaternions
+// qualify but only slerp keeps them normalized
+pub trait Animatable:
+    Lerp + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+}
+
+impl<V> Animatable for V where
+    V: Lerp + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        self + (to - self) * ratio
+    }
+}
+
+impl Lerp for Vector3 {
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        Vector3 {
+            x: self.x.lerp(to.x, ratio),
+            y: self.y.lerp(to.y, ratio),
+            z: self.z.lerp(to.z, ratio),
+        }
+    }
+}
+
+// straight rgba interpolation, no color space conversion
+impl Lerp for Color {
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        Color {
+            r: self.r.lerp(to.r, ratio),
+            g: self.g.lerp(to.g, ratio),
+            b: self.b.lerp(to.b, ratio),
+            a: self.a.lerp(to.a, ratio),
+        }
+    }
+}
+
+// slerp along the shortest arc
+impl Lerp for Quat {
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        let mut dot = self.dot(to);
+        let to = if dot < 0.0 {
+            dot = -dot;
+            to * -1.0
+        } else {
+            to
+        };
+
+        // nearly the same rotation, sin(angle) would be ~0
+        if dot > 0.9995 {
+            return (self * (1.0 - ratio) + to * ratio).normalize();
+        }
+
+        let angle = dot.acos();
+        let sin = angle.sin();
+        let a = ((1.0 - ratio) * angle).sin() / sin;
+        let b = (ratio * angle).sin() / sin;
+        self * a + to * b
+    }
+}
+
+// values relative tweens can move by an offset. rotations compose instead of
+// adding, a quaternion offset is applied in the local frame
+pub trait Offset: Lerp {
+    // the offset that changes nothing
+    fn zero() -> Self;
+    fn offset(self, by: Self) -> Self;
+    // the offset that undoes this one
+    fn inverse(self) -> Self;
+}
+
+impl Offset for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn offset(self, by: Self) -> Self {
+        self + by
+    }
+
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl Offset for Vector3 {
+    fn zero() -> Self {
+        Vector3::ZERO
+    }
+
+    fn offset(self, by: Self) -> Self {
+        self + by
+    }
+
+    fn inverse(self) -> Self {
+        self * -1.0
+    }
+}
+
+impl Offset for Color {
+    fn zero() -> Self {
+        Color::rgba(0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn offset(self, by: Self) -> Self {
+        self + by
+    }
+
+    fn inverse(self) -> Self {
+        self * -1.0
+    }
+}
+
+impl Offset for Quat {
+    fn zero() -> Self {
+        Quat::IDENTITY
+    }
+
+    fn offset(self, by: Self) -> Self {
+        self * by
+    }
+
+    fn inverse(self) -> Self {
+        self.conjugate()
+    }
+}
+
+// animates the `V` that `get` points at inside a `T`, from `from` to `to`.
+// a new property only needs an accessor:
+// `Lens::new(|t: &mut Transform| &mut t.scale.y, 1.0, 2.0)`
+pub struct Lens<T, V> {
+    pub from: V,
+    pub to: V,
+    get: fn(&mut T) -> &mut V,
+}
+
+impl<T, V> Lens<T, V> {
+    pub fn new(get: fn(&mut T) -> &mut V, from: V, to: V) -> Self {
+        Self { from, to, get }
+    }
+}
+
+impl<T, V: Lerp + Debug> TweenTarget<T> for Lens<T, V> {
+    fn lerp(&mut self, target: &mut T, ratio: f32) {
+        *(self.get)(target) = self.from.lerp(self.to, ratio);
+    }
+
+    fn writes(&self, target: &mut T) -> Option<Range<usize>> {
+        field_bytes(target, |target| Some((self.get)(target)))
+    }
+}
+
+impl<T, V: Clone> Clone for Lens<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            from: self.from.clone(),
+            to: self.to.clone(),
+            get: self.get,
+        }
+    }
+}
+
+impl<T, V: Debug> Debug for Lens<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lens")
+            .field("ty", &std::any::type_name::<T>())
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .finish()
+    }
+}
+
+// ready-made lenses for `Transform`
+impl Lens<Transform, Vector3> {
+    pub fn position(from: Vector3, to: Vector3) -> Self {
+        Self::new(|t| &mut t.position, from, to)
+    }
+
+    pub fn scale(from: Vector3, to: Vector3) -> Self {
+        Self::new(|t| &mut t.scale, from, to)
+    }
+}
+
+impl Lens<Transform, f32> {
+    pub fn position_x(from: f32, to: f32) -> Self {
+        Self::new(|t| &mut t.position.x, from, to)
+    }
+
+    pub fn position_y(from: f32, to: f32) -> Self {
+        Self::new(|t| &mut t.position.y, from, to)
+    }
+
+    pub fn position_z(from: f32, to: f32) -> Self {
+        Self::new(|t| &mut t.position.z, from, to)
+    }
+}
+
+impl Lens<Transform, Quat> {
+    pub fn rotation(from: Quat, to: Quat) -> Self {
+        Self::new(|t| &mut t.rotation, from, to)
+    }
+}
+
+// how a `RelativeLens` reads its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Relative {
+    // from wherever the target is when the tween starts to the value
+    To,
+    // from wherever the target is when the tween starts, by the value
+    By,
+    // adds from nothing up to the value on top of what the other tweens
+    // write, several additive tweens on one field add up
+    Additive,
+}
+
+// a lens without a fixed start, so one animation can be played on entities
+// wherever they are. `To` and `By` capture the target's value the first time
+// the tween starts and keep it through loops and seeks, until the animation
+// is reset. `Additive` only does something under an `AnimateComponent`,
+// which layers it over the sequences every frame
+pub struct RelativeLens<T, V> {
+    pub relative: Relative,
+    // the end of a `To`, the offset of `By` and `Additive`
+    pub value: V,
+    start: Option<V>,
+    // what an `Additive` lens adds at the moment
+    added: V,
+    get: fn(&mut T) -> &mut V,
+}
+
+impl<T, V: Offset> RelativeLens<T, V> {
+    pub fn new(get: fn(&mut T) -> &mut V, relative: Relative, value: V) -> Self {
+        Self {
+            relative,
+            value,
+            start: None,
+            added: V::zero(),
+            get,
+        }
+    }
+
+    pub fn to(get: fn(&mut T) -> &mut V, to: V) -> Self {
+        Self::new(get, Relative::To, to)
+    }
+
+    pub fn by(get: fn(&mut T) -> &mut V, by: V) -> Self {
+        Self::new(get, Relative::By, by)
+    }
+
+    pub fn additive(get: fn(&mut T) -> &mut V, by: V) -> Self {
+        Self::new(get, Relative::Additive, by)
+    }
+
+    // the captured start of a `To` or `By`, none before the tween started
+    pub fn start(&self) -> Option<V> {
+        self.start
+    }
+}
+
+impl<T, V: Offset + Debug> TweenTarget<T> for RelativeLens<T, V> {
+    fn lerp(&mut self, target: &mut T, ratio: f32) {
+        if self.relative == Relative::Additive {
+            self.added = V::zero().lerp(self.value, ratio);
+            return;
+        }
+        let value = (self.get)(target);
+        let start = *self.start.get_or_insert(*value);
+        let end = match self.relative {
+            Relative::By => start.offset(self.value),
+            _ => self.value,
+        };
+        *value = start.lerp(end, ratio);
+    }
+
+    // there's nothing to go back to before the first start
+    fn unstarted(&mut self, target: &mut T, ratio: f32) {
+        match self.relative {
+            Relative::Additive => self.added = V::zero(),
+            _ if self.start.is_some() => self.lerp(target, ratio),
+            _ => {}
+        }
+    }
+
+    fn unapply(&mut self, target: &mut T) {
+        if self.relative == Relative::Additive {
+            let value = (self.get)(target);
+            *value = value.offset(self.added.inverse());
+        }
+    }
+
+    fn apply(&mut self, target: &mut T) {
+        if self.relative == Relative::Additive {
+            let value = (self.get)(target);
+            *value = value.offset(self.added);
+        }
+    }
+
+    fn forget(&mut self) {
+        self.start = None;
+        self.added = V::zero();
+    }
+
+    fn additive(&self) -> bool {
+        self.relative == Relative::Additive
+    }
+
+    // an `Additive` lens only writes through `apply`
+    fn writes(&self, target: &mut T) -> Option<Range<usize>> {
+        match self.relative {
+            Relative::Additive => Some(0..0),
+            _ => field_bytes(target, |target| Some((self.get)(target))),
+        }
+    }
+}
+
+impl<T, V: Clone> Clone for RelativeLens<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            relative: self.relative,
+            value: self.value.clone(),
+            start: self.start.clone(),
+            added: self.added.clone(),
+            get: self.get,
+        }
+    }
+}
+
+impl<T, V: Debug> Debug for RelativeLens<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RelativeLens")
+            .field("ty", &std::any::type_name::<T>())
+            .field("relative", &self.relative)
+            .field("value", &self.value)
+            .field("start", &self.start)
+            .field("added", &self.added)
+            .finish()
+    }
+}
+
+// ready-made relative lenses for `Transform`, e.g. a nudge to the right that
+// works on any entity: `RelativeLens::position_x(Relative::By, 1.0)`
+impl RelativeLens<Transform, Vector3> {
+    pub fn position(relative: Relative, value: Vector3) -> Self {
+        Self::new(|t| &mut t.position, relative, value)
+    }
+
+    pub fn scale(relative: Relative, value: Vector3) -> Self {
+        Self::new(|t| &mut t.scale, relative, value)
+    }
+}
+
+impl RelativeLens<Transform, f32> {
+    pub fn position_x(relative: Relative, value: f32) -> Self {
+        Self::new(|t| &mut t.position.x, relative, value)
+    }
+
+    pub fn position_y(relative: Relative, value: f32) -> Self {
+        Self::new(|t| &mut t.position.y, relative, value)
+    }
+
+    pub fn position_z(relative: Relative, value: f32) -> Self {
+        Self::new(|t| &mut t.position.z, relative, value)
+    }
+}
+
+impl RelativeLens<Transform, Quat> {
+    pub fn rotation(relative: Relative, value: Quat) -> Self {
+        Self::new(|t| &mut t.rotation, relative, value)
+    }
+}
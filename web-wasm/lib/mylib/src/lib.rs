@@ -9,35 +9,293 @@ use unsafe_send_sync::UnsafeSendSync;
 use wasm_bindgen::prelude::*;
 
 use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
 use serde::{Deserialize, Serialize};
 use web_sys::console;
 
 #[macro_use]
 extern crate lazy_static;
 
-#[macro_use]
-mod animation;
+pub mod action;
+pub mod animation;
+pub mod clip;
+pub mod curve;
+pub mod keyframe;
+pub mod layer;
+pub mod lens;
+pub mod plugin;
+pub mod property;
 mod renderer;
+pub mod spring;
+pub mod state_machine;
 
 use animation::*;
+use clip::*;
+use layer::{AnimationLayer, BlendMode};
+use lens::*;
+use plugin::{AnimationPlugin, AnimationSet, Plugin};
 use renderer::*;
+use state_machine::{AnimationStateMachine, Param, StateMachineError};
 use winit::{
     event::{self, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
 };
 
 #[wasm_bindgen]
-#[derive(Component, Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Component, Reflect, Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
     pub z: f32,
 }
 
+impl Vector3 {
+    pub const ZERO: Vector3 = Vector3::splat(0.0);
+    pub const ONE: Vector3 = Vector3::splat(1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub const fn splat(v: f32) -> Self {
+        Self::new(v, v, v)
+    }
+
+    pub fn dot(self, other: Vector3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vector3) -> Vector3 {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    // zero stays zero
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+        if length > 0.0 {
+            self * (1.0 / length)
+        } else {
+            self
+        }
+    }
+}
+
+impl std::ops::Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl std::ops::Sub for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl std::ops::Mul<f32> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, s: f32) -> Vector3 {
+        Vector3::new(self.x * s, self.y * s, self.z * s)
+    }
+}
+
+// unit quaternion, same layout as three.js
+#[wasm_bindgen]
+#[derive(Reflect, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    // `axis` has to be normalized, `angle` is in radians
+    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self {
+            x: axis.x * sin,
+            y: axis.y * sin,
+            z: axis.z * sin,
+            w: cos,
+        }
+    }
+
+    // the shortest rotation taking the direction `from` to `to`, both
+    // normalized. opposite directions turn around an arbitrary axis
+    pub fn from_rotation_arc(from: Vector3, to: Vector3) -> Self {
+        let dot = from.dot(to);
+        if dot < -0.999999 {
+            let axis = from.cross(Vector3::new(1.0, 0.0, 0.0));
+            let axis = if axis.length() < 1e-6 {
+                from.cross(Vector3::new(0.0, 1.0, 0.0))
+            } else {
+                axis
+            };
+            return Self::from_axis_angle(axis.normalize(), std::f32::consts::PI);
+        }
+        let axis = from.cross(to);
+        Quat {
+            x: axis.x,
+            y: axis.y,
+            z: axis.z,
+            w: 1.0 + dot,
+        }
+        .normalize()
+    }
+
+    pub fn dot(self, other: Quat) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn normalize(self) -> Self {
+        self * (1.0 / self.dot(self).sqrt())
+    }
+
+    // the inverse rotation of a normalized quaternion
+    pub fn conjugate(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        }
+    }
+}
+
+impl std::ops::Add for Quat {
+    type Output = Quat;
+
+    fn add(self, other: Quat) -> Quat {
+        Quat {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+            w: self.w + other.w,
+        }
+    }
+}
+
+impl std::ops::Sub for Quat {
+    type Output = Quat;
+
+    fn sub(self, other: Quat) -> Quat {
+        self + other * -1.0
+    }
+}
+
+// hamilton product, `a * b` rotates by `b` then by `a`
+impl std::ops::Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, o: Quat) -> Quat {
+        Quat {
+            x: self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            y: self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            z: self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+            w: self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+        }
+    }
+}
+
+impl std::ops::Mul<f32> for Quat {
+    type Output = Quat;
+
+    fn mul(self, s: f32) -> Quat {
+        Quat {
+            x: self.x * s,
+            y: self.y * s,
+            z: self.z * s,
+            w: self.w * s,
+        }
+    }
+}
+
+// linear rgba, every channel in [0, 1]
+#[wasm_bindgen]
+#[derive(Component, Reflect, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+}
+
+impl std::ops::Add for Color {
+    type Output = Color;
+
+    fn add(self, other: Color) -> Color {
+        Color::rgba(
+            self.r + other.r,
+            self.g + other.g,
+            self.b + other.b,
+            self.a + other.a,
+        )
+    }
+}
+
+impl std::ops::Sub for Color {
+    type Output = Color;
+
+    fn sub(self, other: Color) -> Color {
+        self + other * -1.0
+    }
+}
+
+impl std::ops::Mul<f32> for Color {
+    type Output = Color;
+
+    fn mul(self, s: f32) -> Color {
+        Color::rgba(self.r * s, self.g * s, self.b * s, self.a * s)
+    }
+}
+
 #[wasm_bindgen]
-#[derive(Serialize, Deserialize, Component, Debug)]
-struct Transform {
+#[derive(Serialize, Deserialize, Component, Reflect, Clone, Debug)]
+pub struct Transform {
     pub position: Vector3,
+    pub rotation: Quat,
+    pub scale: Vector3,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            position: Vector3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vector3::ONE,
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -53,17 +311,6 @@ fn request_animation_frame(f: &Closure<dyn FnMut()>) {
         .expect("should register `requestAnimationFrame` OK");
 }
 
-#[derive(Clone, Debug)]
-pub struct TransformPositionXTween {
-    start: f32,
-    end: f32,
-}
-impl TweenTarget<Transform> for TransformPositionXTween {
-    fn lerp(&mut self, target: &mut Transform, ratio: f32) {
-        target.position.x = self.start + (self.end - self.start) * ratio;
-    }
-}
-
 fn update_transform_sys(mut query: Query<(&Transform, &mut Mesh)>) {
     let js_sys = JS_SYSTEMS.borrow_mut();
     let js_func = match js_sys.get("update_transform_sys") {
@@ -83,6 +330,29 @@ fn update_transform_sys(mut query: Query<(&Transform, &mut Mesh)>) {
     }
 }
 
+// hands the animation events to the JS systems of the same name, if any
+#[cfg(target_arch = "wasm32")]
+fn animation_events_sys(
+    mut tween_completed: EventReader<TweenCompleted>,
+    mut sequence_completed: EventReader<SequenceCompleted>,
+    mut looped: EventReader<AnimationLooped>,
+) {
+    fn call<E: Serialize>(name: &str, events: impl Iterator<Item = E>) {
+        // cloned out like in `run_js_calls`, a callback may register systems
+        let Some(js_func) = JS_SYSTEMS.borrow().get(name).cloned() else {
+            return;
+        };
+        for event in events {
+            let event = serde_wasm_bindgen::to_value(&event).unwrap();
+            js_func.call1(&JsValue::null(), &event).unwrap();
+        }
+    }
+
+    call("tween_completed_sys", tween_completed.iter());
+    call("sequence_completed_sys", sequence_completed.iter());
+    call("animation_looped_sys", looped.iter());
+}
+
 lazy_static! {
     pub static ref WORLD: RwLock<World> = RwLock::new(World::new());
     pub static ref SCHEDULE: RwLock<Schedule> = RwLock::new(Schedule::default());
@@ -97,13 +367,13 @@ pub fn create_entity_with_mesh(mesh_index: u32) {
         seq.then(Tween::new(
             EasingFunction::QuadraticIn,
             2000.0,
-            TransformPositionXTween { start: 0., end: 5. },
+            Lens::position_x(0., 5.),
         ))
         .then_delay(2000.)
         .then(Tween::new(
             EasingFunction::QuadraticIn,
             2000.0,
-            TransformPositionXTween { start: 5., end: 0. },
+            Lens::position_x(5., 0.),
         ));
 
         let mut animate = AnimateComponent::new();
@@ -111,13 +381,7 @@ pub fn create_entity_with_mesh(mesh_index: u32) {
 
         //spwan entity
         world.spawn((
-            Transform {
-                position: Vector3 {
-                    x: 0.0,
-                    y: 0.0,
-                    z: 0.0,
-                },
-            },
+            Transform::default(),
             Mesh { mesh_index },
             // Add an Animator component to control and execute the animation.
             animate,
@@ -131,13 +395,183 @@ pub fn insert_resource(res: JsValue) {
     world.insert_non_send_resource(res);
 }
 
+// parses a clip (see `clip::Clip` for the format) and keeps it under its name
+#[wasm_bindgen]
+pub fn load_clip(json: &str) -> Result<(), JsValue> {
+    let clip = Clip::from_json(json).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    let mut world = WORLD.write().unwrap();
+    world.resource_mut::<ClipLibrary>().insert(clip);
+    Ok(())
+}
+
+fn build_clip(world: &World, name: &str) -> Result<AnimateComponent<Transform>, JsValue> {
+    match world.resource::<ClipLibrary>().get(name) {
+        Some(clip) => clip
+            .build()
+            .map_err(|err| JsValue::from_str(&err.to_string())),
+        None => Err(JsValue::from_str(&format!("no clip named {:?}", name))),
+    }
+}
+
+fn mesh_entity(world: &mut World, mesh_index: u32) -> Result<Entity, JsValue> {
+    world
+        .query::<(Entity, &Mesh)>()
+        .iter(world)
+        .find(|(_, mesh)| mesh.mesh_index == mesh_index)
+        .map(|(entity, _)| entity)
+        .ok_or_else(|| JsValue::from_str(&format!("no entity with mesh {}", mesh_index)))
+}
+
+// replaces the animation of the entity showing `mesh_index` with a loaded clip
+#[wasm_bindgen]
+pub fn play_clip(mesh_index: u32, name: &str) -> Result<(), JsValue> {
+    let mut world = WORLD.write().unwrap();
+    let animate = build_clip(&world, name)?;
+    let entity = mesh_entity(&mut world, mesh_index)?;
+
+    world.entity_mut(entity).insert(animate);
+    world.resource_mut::<AnimationSystemInfo>().needsUpdate = true;
+    Ok(())
+}
+
+// fades the entity showing `mesh_index` from what it plays to a loaded clip
+// in `duration` ms, the clip starts from its beginning
+#[wasm_bindgen]
+pub fn crossfade_clip(mesh_index: u32, name: &str, duration: f32) -> Result<(), JsValue> {
+    let mut world = WORLD.write().unwrap();
+    let mut layer = AnimationLayer::new(BlendMode::Override, 1.0);
+    layer.sequences = build_clip(&world, name)?.sequences;
+    let entity = mesh_entity(&mut world, mesh_index)?;
+
+    match world.get_mut::<AnimateComponent<Transform>>(entity) {
+        Some(mut animate) => {
+            animate.crossfade(layer, duration);
+        }
+        None => {
+            let mut animate = AnimateComponent::new();
+            animate.crossfade(layer, duration);
+            world.entity_mut(entity).insert(animate);
+        }
+    }
+    world.resource_mut::<AnimationSystemInfo>().needsUpdate = true;
+    Ok(())
+}
+
+// puts a state machine (see `state_machine` for the JSON) on the entity
+// showing `mesh_index`, it starts in its initial state on the next frame
+#[wasm_bindgen]
+pub fn load_state_machine(mesh_index: u32, json: &str) -> Result<(), JsValue> {
+    let machine = AnimationStateMachine::from_json(json)
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+    let mut world = WORLD.write().unwrap();
+    let entity = mesh_entity(&mut world, mesh_index)?;
+
+    let mut entity = world.entity_mut(entity);
+    if !entity.contains::<AnimateComponent<Transform>>() {
+        entity.insert(AnimateComponent::<Transform>::new());
+    }
+    entity.insert(machine);
+    Ok(())
+}
+
+fn state_machine(
+    world: &mut World,
+    mesh_index: u32,
+) -> Result<Mut<'_, AnimationStateMachine>, JsValue> {
+    let entity = mesh_entity(world, mesh_index)?;
+    world
+        .get_mut::<AnimationStateMachine>(entity)
+        .ok_or_else(|| JsValue::from_str(&format!("no state machine on mesh {}", mesh_index)))
+}
+
+// a boolean for bool parameters, a number for floats, anything for triggers
+#[wasm_bindgen]
+pub fn set_state_param(mesh_index: u32, name: &str, value: JsValue) -> Result<(), JsValue> {
+    let mut world = WORLD.write().unwrap();
+    let mut machine = state_machine(&mut world, mesh_index)?;
+    let to_js = |err: StateMachineError| JsValue::from_str(&err.to_string());
+    let declared = machine.lookup_param(name).map_err(to_js)?;
+    let param = match declared {
+        Param::Bool(_) => value.as_bool().map(Param::Bool),
+        Param::Float(_) => value.as_f64().map(|value| Param::Float(value as f32)),
+        Param::Trigger(_) => Some(Param::Trigger(true)),
+    };
+    let param = param.ok_or_else(|| StateMachineError::WrongType {
+        name: name.to_string(),
+        expected: declared.kind(),
+    });
+    param
+        .and_then(|param| machine.set_param(name, param))
+        .map_err(to_js)
+}
+
+#[wasm_bindgen]
+pub fn animation_state(mesh_index: u32) -> Result<String, JsValue> {
+    let mut world = WORLD.write().unwrap();
+    let machine = state_machine(&mut world, mesh_index)?;
+    Ok(machine.state().to_string())
+}
+
+// jumps the clock of the entity showing `mesh_index` to one of its labels:
+// its `AnimationPlayer` if it has one, the global clock otherwise
+#[wasm_bindgen]
+pub fn seek_to_label(mesh_index: u32, name: &str) -> Result<(), JsValue> {
+    let mut world = WORLD.write().unwrap();
+    let entity = mesh_entity(&mut world, mesh_index)?;
+    let time = world
+        .get::<AnimateComponent<Transform>>(entity)
+        .and_then(|animate| animate.label_time(name))
+        .ok_or_else(|| JsValue::from_str(&format!("no label named {:?}", name)))?;
+
+    match world.get_mut::<AnimationPlayer>(entity) {
+        Some(mut player) => player.seek(time),
+        None => world.resource_mut::<AnimationSystemInfo>().seek(time),
+    }
+    Ok(())
+}
+
+// 1.0 is real time, negative values play every animation backwards
+#[wasm_bindgen]
+pub fn set_time_scale(scale: f32) {
+    let mut world = WORLD.write().unwrap();
+    let mut animation_info = world.get_resource_mut::<AnimationSystemInfo>().unwrap();
+    animation_info.timeScale = scale;
+}
+
 lazy_static! {
     static ref JS_SYSTEMS: UnsafeSendSync<RefCell<HashMap<String, Function>>> =
         UnsafeSendSync::new(RefCell::new(HashMap::new()));
 }
 
+lazy_static! {
+    // `Action::Js` calls fired while the schedule holds the world
+    static ref JS_CALLS: Mutex<Vec<(String, u32)>> = Mutex::new(Vec::new());
+}
+
+// calls the JS system registered as `name` with `tag` once the world is
+// released, the JS side may call back into it
+pub(crate) fn queue_js_call(name: &str, tag: u32) {
+    JS_CALLS.lock().unwrap().push((name.into(), tag));
+}
+
+// runs the queued `Action::Js` calls in order, after `schedule.run` with the
+// world lock released. a JS system that isn't registered is skipped
+pub fn run_js_calls() {
+    let calls = std::mem::take(&mut *JS_CALLS.lock().unwrap());
+    for (name, tag) in calls {
+        // cloned out, the function may register systems itself
+        let func = JS_SYSTEMS.borrow().get(&name).cloned();
+        if let Some(func) = func {
+            func.call1(&JsValue::null(), &JsValue::from(tag)).unwrap();
+        }
+    }
+}
+
+// JS systems drive the animations by default, they see the frame before it
+// plays. `after_animation` runs them once it has played instead, for the
+// ones that read the animated values, like a render system
 #[wasm_bindgen]
-pub fn add_system(func: &js_sys::Function, independent: bool) {
+pub fn add_system(func: &js_sys::Function, independent: bool, after_animation: bool) {
     JS_SYSTEMS
         .borrow_mut()
         .insert(func.name().into(), func.clone());
@@ -145,14 +579,18 @@ pub fn add_system(func: &js_sys::Function, independent: bool) {
     if independent {
         let func_name: String = func.name().into();
         let fc = move || {
-            let js_syss = JS_SYSTEMS.borrow();
-            let func = js_syss.get(&func_name).unwrap();
+            // cloned out, the function may register systems itself
+            let func = JS_SYSTEMS.borrow().get(&func_name).cloned().unwrap();
             func.apply(&JsValue::null(), &Array::new()).unwrap();
             // console::log_1(&format!("func{:?}", func).into());
         };
 
         let mut schedule = SCHEDULE.write().unwrap();
-        schedule.add_system(fc);
+        if after_animation {
+            schedule.add_system(fc.after(AnimationSet::Sync));
+        } else {
+            schedule.add_system(fc.before(AnimationSet::Clock));
+        }
     }
 }
 
@@ -172,18 +610,25 @@ pub async fn setup() -> (winit::window::Window, EventLoop<()>) {
         let sprite_renderer = SpriteRenderer::new(&window).await;
         world.insert_non_send_resource(sprite_renderer);
         world.insert_resource(AnimationSystemInfo::default());
+        world.init_resource::<ClipLibrary>();
     }
     // Create a new Schedule, which defines an execution strategy for Systems
     {
         let mut schedule = SCHEDULE.write().unwrap();
         schedule.set_executor_kind(bevy_ecs::schedule::ExecutorKind::SingleThreaded);
 
+        AnimationPlugin::<Transform>::new().build(&mut WORLD.write().unwrap(), &mut schedule);
+        schedule.add_system(
+            state_machine::animation_state_machine_sys
+                .in_set(AnimationSet::Controller)
+                .before(animation_controller_sys::<Transform>),
+        );
         #[cfg(target_arch = "wasm32")]
         {
-            schedule.add_system(update_transform_sys);
-            schedule.add_system(update_time);
+            schedule.add_system(update_transform_sys.in_set(AnimationSet::Sync));
+            schedule.add_system(update_time.in_set(AnimationSet::Sync));
+            schedule.add_system(animation_events_sys.in_set(AnimationSet::Sync));
         }
-        add_to_animation_sys!(schedule, Transform);
 
         //one time sys
         {
@@ -213,6 +658,7 @@ pub async fn run() {
                 let mut schedule = SCHEDULE.write().unwrap();
                 schedule.run(&mut world);
             }
+            run_js_calls();
             request_animation_frame(f.borrow().as_ref().unwrap());
         }));
         request_animation_frame(g.borrow().as_ref().unwrap());
@@ -262,9 +708,13 @@ pub async fn run() {
                     _ => {}
                 },
                 event::Event::RedrawRequested(_) => {
-                    let mut world: &mut bevy_ecs::world::World = { &mut *WORLD.write().unwrap() };
-                    let mut schedule = SCHEDULE.write().unwrap();
-                    schedule.run(&mut world);
+                    {
+                        let mut world: &mut bevy_ecs::world::World =
+                            { &mut *WORLD.write().unwrap() };
+                        let mut schedule = SCHEDULE.write().unwrap();
+                        schedule.run(&mut world);
+                    }
+                    run_js_calls();
 
                     // let frame = match surface.get_current_texture() {
                     //     Ok(frame) => frame,
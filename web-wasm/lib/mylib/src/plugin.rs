@@ -0,0 +1,107 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use bevy_ecs::prelude::{
+    Component, IntoSystemConfig, IntoSystemSetConfigs, Resource, Schedule, SystemSet, World,
+};
+
+use crate::animation::{
+    add_animation_events, animation_clock_sys, animation_controller_sys,
+    animation_resource_controller_sys, animation_resource_sys, animation_sys,
+    animation_updated_sys, AnimationSystemInfo,
+};
+
+// the stages of an animation frame, in this order for every animated type,
+// so every total is known before anything is sampled. systems that drive the
+// animations go `.before(AnimationSet::Clock)`, the ones that read the
+// animated values `.after(AnimationSet::Evaluate)`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationSet {
+    // advances the global time and the players, rotates the event buffers
+    Clock,
+    // picks what plays and measures how long it lasts
+    Controller,
+    // samples the sequences into the components and resources
+    Evaluate,
+    // hands the results on, to the renderer or to JS
+    Sync,
+}
+
+// there is no `App` here, a plugin adds its systems to a schedule and what
+// they need to the world. the first one built on a world also adds the clock,
+// the events and the order of the sets, build every plugin of a world on the
+// same schedule
+pub trait Plugin {
+    fn build(&self, world: &mut World, schedule: &mut Schedule);
+}
+
+// marks a world that already has the clock
+#[derive(Resource)]
+struct AnimationCore;
+
+fn add_core(world: &mut World, schedule: &mut Schedule) {
+    if world.contains_resource::<AnimationCore>() {
+        return;
+    }
+    world.insert_resource(AnimationCore);
+    world.init_resource::<AnimationSystemInfo>();
+    add_animation_events(world, schedule);
+    schedule.configure_sets(
+        (
+            AnimationSet::Clock,
+            AnimationSet::Controller,
+            AnimationSet::Evaluate,
+            AnimationSet::Sync,
+        )
+            .chain(),
+    );
+    schedule.add_system(animation_clock_sys.in_set(AnimationSet::Clock));
+    schedule.add_system(animation_updated_sys.in_set(AnimationSet::Sync));
+}
+
+// animates the component `T` through its `AnimateComponent<T>`
+pub struct AnimationPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> AnimationPlugin<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for AnimationPlugin<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Component + Clone + Debug> Plugin for AnimationPlugin<T> {
+    fn build(&self, world: &mut World, schedule: &mut Schedule) {
+        add_core(world, schedule);
+        schedule.add_system(animation_controller_sys::<T>.in_set(AnimationSet::Controller));
+        schedule.add_system(animation_sys::<T>.in_set(AnimationSet::Evaluate));
+    }
+}
+
+// animates the resource `T` through its `AnimateResource<T>`
+pub struct AnimationResourcePlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> AnimationResourcePlugin<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for AnimationResourcePlugin<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Resource + Clone> Plugin for AnimationResourcePlugin<T> {
+    fn build(&self, world: &mut World, schedule: &mut Schedule) {
+        add_core(world, schedule);
+        schedule
+            .add_system(animation_resource_controller_sys::<T>.in_set(AnimationSet::Controller));
+        schedule.add_system(animation_resource_sys::<T>.in_set(AnimationSet::Evaluate));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use bevy_reflect::{ParsedPath, Reflect};
+
+use crate::animation::{field_bytes, TweenTarget};
+use crate::lens::Lerp;
+
+// a field of any reflected component, addressed by a string such as
+// "Transform.position.y": the component's type name, then a bevy_reflect
+// path into it. the path is checked once when the lens is made, not on
+// every frame
+pub struct PropertyLens<T, V> {
+    pub from: V,
+    pub to: V,
+    path: String,
+    field: ParsedPath,
+    ty: PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError {
+    // no field after the component name
+    Malformed {
+        path: String,
+    },
+    // the path starts with another component than the animated one
+    WrongComponent {
+        path: String,
+        expected: String,
+    },
+    // the field doesn't exist
+    Unresolved {
+        path: String,
+        reason: String,
+    },
+    // the field exists but doesn't hold the tweened values
+    WrongType {
+        path: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::Malformed { path } => {
+                write!(f, "`{}` should look like `Component.field`", path)
+            }
+            PathError::WrongComponent { path, expected } => {
+                write!(
+                    f,
+                    "`{}` does not start with the animated component `{}`",
+                    path, expected
+                )
+            }
+            PathError::Unresolved { path, reason } => {
+                write!(f, "`{}` does not resolve: {}", path, reason)
+            }
+            PathError::WrongType {
+                path,
+                expected,
+                found,
+            } => write!(f, "`{}` is a `{}`, not a `{}`", path, found, expected),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+// a type name without its module, what paths start with
+fn short_name(name: &str) -> &str {
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl<T, V> PropertyLens<T, V>
+where
+    T: Reflect + Default,
+    V: Reflect,
+{
+    // resolves `path` on a default `T` to catch typos and type mismatches
+    pub fn new(path: &str, from: V, to: V) -> Result<Self, PathError> {
+        let expected = short_name(std::any::type_name::<T>());
+        let Some((component, field)) = path.split_once('.') else {
+            return Err(PathError::Malformed { path: path.into() });
+        };
+        if field.is_empty() {
+            return Err(PathError::Malformed { path: path.into() });
+        }
+        if component != expected {
+            return Err(PathError::WrongComponent {
+                path: path.into(),
+                expected: expected.into(),
+            });
+        }
+
+        let unresolved = |reason: String| PathError::Unresolved {
+            path: path.into(),
+            reason,
+        };
+        let mut parsed = ParsedPath::parse(field).map_err(|err| unresolved(err.to_string()))?;
+        let mut sample = T::default();
+        let value = parsed
+            .reflect_element_mut(&mut sample)
+            .map_err(|err| unresolved(err.to_string()))?;
+        if !value.is::<V>() {
+            return Err(PathError::WrongType {
+                path: path.into(),
+                expected: short_name(std::any::type_name::<V>()).into(),
+                found: short_name(value.type_name()).into(),
+            });
+        }
+
+        Ok(Self {
+            from,
+            to,
+            path: path.into(),
+            field: parsed,
+            ty: PhantomData,
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl<T, V> TweenTarget<T> for PropertyLens<T, V>
+where
+    T: Reflect,
+    V: Lerp + Reflect + fmt::Debug,
+{
+    fn lerp(&mut self, target: &mut T, ratio: f32) {
+        // checked in `new`, the path can only miss if `T` changed shape
+        if let Ok(value) = self.field.element_mut::<V>(target) {
+            *value = self.from.lerp(self.to, ratio);
+        }
+    }
+
+    fn writes(&self, target: &mut T) -> Option<Range<usize>> {
+        let mut field = self.field.clone();
+        field_bytes(target, |target| field.element_mut::<V>(target).ok())
+    }
+}
+
+impl<T, V: Clone> Clone for PropertyLens<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            from: self.from.clone(),
+            to: self.to.clone(),
+            path: self.path.clone(),
+            field: self.field.clone(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T, V: fmt::Debug> fmt::Debug for PropertyLens<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertyLens")
+            .field("path", &self.path)
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .finish()
+    }
+}
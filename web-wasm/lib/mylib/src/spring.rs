@@ -0,0 +1,304 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::animation::{field_bytes, EasingFunction, Tweenable};
+use crate::lens::Animatable;
+use crate::{Color, Quat, Vector3};
+
+// a damped spring in physical units: stiffness in N/m, damping in N·s/m
+// and mass in kg, over seconds while the rest of the crate counts ms
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+}
+
+impl SpringConfig {
+    // the usual ui presets
+    pub const DEFAULT: Self = Self::new(170.0, 26.0, 1.0);
+    pub const GENTLE: Self = Self::new(120.0, 14.0, 1.0);
+    pub const WOBBLY: Self = Self::new(180.0, 12.0, 1.0);
+    pub const STIFF: Self = Self::new(210.0, 20.0, 1.0);
+
+    pub const fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass,
+        }
+    }
+
+    // undamped angular frequency and damping ratio, under 1 it oscillates
+    fn omega_zeta(&self) -> (f32, f32) {
+        let omega = (self.stiffness / self.mass).sqrt();
+        let zeta = self.damping / (2.0 * (self.stiffness * self.mass).sqrt());
+        (omega, zeta)
+    }
+
+    // the spring at `t` seconds is `to + (from - to) * a + velocity * b`
+    // and its velocity `(from - to) * da + velocity * db`, returns
+    // `(a, b, da, db)`
+    fn response(&self, t: f32) -> (f32, f32, f32, f32) {
+        let (omega, zeta) = self.omega_zeta();
+        if (zeta - 1.0).abs() < 1e-3 {
+            // critically damped
+            let decay = (-omega * t).exp();
+            (
+                (1.0 + omega * t) * decay,
+                t * decay,
+                -omega * omega * t * decay,
+                (1.0 - omega * t) * decay,
+            )
+        } else if zeta < 1.0 {
+            let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+            let decay = (-zeta * omega * t).exp();
+            let (sin, cos) = (omega_d * t).sin_cos();
+            (
+                decay * (cos + zeta * omega / omega_d * sin),
+                decay * sin / omega_d,
+                -decay * omega * omega / omega_d * sin,
+                decay * (cos - zeta * omega / omega_d * sin),
+            )
+        } else {
+            let root = (zeta * zeta - 1.0).sqrt();
+            let (r1, r2) = (-omega * (zeta - root), -omega * (zeta + root));
+            let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+            let d = r1 - r2;
+            (
+                (r1 * e2 - r2 * e1) / d,
+                (e1 - e2) / d,
+                r1 * r2 * (e2 - e1) / d,
+                (r1 * e1 - r2 * e2) / d,
+            )
+        }
+    }
+
+    // ms until the decay of the slowest mode brings a unit displacement
+    // under `precision`, infinite without damping
+    pub fn settle_time(&self, precision: f32) -> f32 {
+        self.settle_time_from(precision, 1.0, 0.0)
+    }
+
+    // the same from `displacement` away with `speed` per second, both
+    // magnitudes. the velocity part of the response is at most about
+    // `speed / omega` times the displacement part
+    pub fn settle_time_from(&self, precision: f32, displacement: f32, speed: f32) -> f32 {
+        let (omega, zeta) = self.omega_zeta();
+        let log = ((displacement + speed / omega) / precision).ln();
+        let seconds = if log <= 0.0 {
+            // already within precision
+            0.0
+        } else if zeta <= 0.0 {
+            f32::INFINITY
+        } else if (zeta - 1.0).abs() < 1e-3 {
+            // solves (1 + x) e^-x = precision closely enough
+            (log + (1.0 + log).ln()) / omega
+        } else if zeta < 1.0 {
+            log / (zeta * omega)
+        } else {
+            log / (omega * (zeta - (zeta * zeta - 1.0).sqrt()))
+        };
+        seconds * 1000.0
+    }
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// from `from` with `velocity` towards `to`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Segment<V> {
+    // ms from the start of the spring
+    start: f32,
+    from: V,
+    // per second
+    velocity: V,
+    to: V,
+}
+
+// how far a spring value is from zero, scales its settle time
+pub trait Magnitude {
+    fn magnitude(self) -> f32;
+}
+
+impl Magnitude for f32 {
+    fn magnitude(self) -> f32 {
+        self.abs()
+    }
+}
+
+impl Magnitude for Vector3 {
+    fn magnitude(self) -> f32 {
+        self.length()
+    }
+}
+
+impl Magnitude for Quat {
+    fn magnitude(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl Magnitude for Color {
+    fn magnitude(self) -> f32 {
+        (self.r * self.r + self.g * self.g + self.b * self.b + self.a * self.a).sqrt()
+    }
+}
+
+// the `V` that `get` points at pulled to its target by a spring. its
+// duration is the estimated settle time, after which it snaps to the target.
+// `retarget` changes the target on the way without losing speed, the spring
+// is still a function of time so seeking works as for any tween. reach it
+// inside a sequence with `downcast_mut`:
+// `seq.tweens_mut()[0].downcast_mut::<Spring<Transform, f32>>()`. going
+// through the component's `Mut` marks it changed, so a global clock
+// measures the longer settle again
+pub struct Spring<T, V> {
+    // absolute
+    pub start_abs: f32,
+    pub config: SpringConfig,
+    // what's left of the displacement when the spring counts as settled
+    pub precision: f32,
+    // the first one starts at 0, sorted by start
+    segments: Vec<Segment<V>>,
+    get: fn(&mut T) -> &mut V,
+}
+
+impl<T, V: Animatable + Magnitude> Spring<T, V> {
+    pub fn new(get: fn(&mut T) -> &mut V, config: SpringConfig, from: V, to: V) -> Self {
+        Self {
+            start_abs: 0.0,
+            config,
+            precision: 0.001,
+            segments: vec![Segment {
+                start: 0.0,
+                from,
+                velocity: from * 0.0,
+                to,
+            }],
+            get,
+        }
+    }
+
+    // starts already moving, in units per second
+    pub fn with_velocity(mut self, velocity: V) -> Self {
+        self.segments[0].velocity = velocity;
+        self
+    }
+
+    pub fn target(&self) -> V {
+        self.segments[self.segments.len() - 1].to
+    }
+
+    // position and velocity per second at an absolute time
+    pub fn state_at(&self, time: f32) -> (V, V) {
+        let local = (time - self.start_abs).max(0.0);
+        let index = self.segments.partition_point(|s| s.start <= local) - 1;
+        let segment = &self.segments[index];
+        if index == self.segments.len() - 1 && local >= self.end() {
+            return (segment.to, segment.velocity * 0.0);
+        }
+
+        let (a, b, da, db) = self.config.response((local - segment.start) / 1000.0);
+        let offset = segment.from - segment.to;
+        (
+            segment.to + offset * a + segment.velocity * b,
+            offset * da + segment.velocity * db,
+        )
+    }
+
+    // settle time of the last segment, in ms from the start
+    fn end(&self) -> f32 {
+        let last = &self.segments[self.segments.len() - 1];
+        let displacement = (last.from - last.to).magnitude();
+        let speed = last.velocity.magnitude();
+        last.start
+            + self
+                .config
+                .settle_time_from(self.precision, displacement, speed)
+    }
+
+    // heads for `to` from where the spring is at the absolute `time`, with
+    // the velocity it has there. a retarget before earlier ones replaces them
+    pub fn retarget(&mut self, time: f32, to: V) {
+        let local = (time - self.start_abs).max(0.0);
+        let (from, velocity) = self.state_at(time);
+        self.segments.retain(|s| s.start < local);
+        self.segments.push(Segment {
+            start: local,
+            from,
+            velocity,
+            to,
+        });
+    }
+}
+
+impl<T, V> Tweenable<T> for Spring<T, V>
+where
+    T: 'static,
+    V: Animatable + Magnitude + Debug + Send + Sync + 'static,
+{
+    fn duration(&self) -> f32 {
+        self.end()
+    }
+
+    fn start_abs(&self) -> f32 {
+        self.start_abs
+    }
+
+    fn set_start_abs(&mut self, v: f32) {
+        self.start_abs = v;
+    }
+
+    // an undamped spring has no end, the ratio covers its first second
+    fn lerp(&mut self, comp: &mut T, ratio: f32) {
+        let duration = self.duration();
+        let duration = if duration.is_finite() {
+            duration
+        } else {
+            1000.0
+        };
+        self.sample(comp, self.start_abs + ratio * duration);
+    }
+
+    // the spring does the shaping
+    fn ease(&self) -> EasingFunction {
+        EasingFunction::Linear
+    }
+
+    fn sample(&mut self, comp: &mut T, time: f32) {
+        *(self.get)(comp) = self.state_at(time).0;
+    }
+
+    fn writes(&self, comp: &mut T) -> Option<Range<usize>> {
+        field_bytes(comp, |comp| Some((self.get)(comp)))
+    }
+}
+
+impl<T, V: Clone> Clone for Spring<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            start_abs: self.start_abs,
+            config: self.config,
+            precision: self.precision,
+            segments: self.segments.clone(),
+            get: self.get,
+        }
+    }
+}
+
+impl<T, V: Debug> Debug for Spring<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spring")
+            .field("ty", &std::any::type_name::<T>())
+            .field("start_abs", &self.start_abs)
+            .field("config", &self.config)
+            .field("precision", &self.precision)
+            .field("segments", &self.segments)
+            .finish()
+    }
+}
@@ -0,0 +1,357 @@
+// switches the clip an entity plays from named states, following
+// transitions guarded by parameters that systems or JS set. every state
+// plays a clip of the `ClipLibrary` and a transition crossfades to the clip
+// of the state it goes to. machines can be written as JSON:
+//
+// {
+//     "initial": "idle",
+//     "states": { "idle": "idle_clip", "walk": "walk_clip", "jump": "jump_clip" },
+//     "params": { "speed": { "Float": 0 }, "jump": { "Trigger": false } },
+//     "transitions": [
+//         { "from": "idle", "to": "walk", "duration": 200,
+//           "conditions": [{ "Greater": ["speed", 0.1] }] },
+//         { "to": "jump", "duration": 50, "conditions": [{ "Triggered": "jump" }] },
+//         { "from": "jump", "to": "idle", "duration": 200, "conditions": ["Finished"] }
+//     ]
+// }
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bevy_ecs::prelude::{Component, Query, Res, ResMut};
+use serde::{Deserialize, Serialize};
+
+use crate::animation::{AnimateComponent, AnimationPlayer, AnimationSystemInfo};
+use crate::clip::ClipLibrary;
+use crate::layer::{AnimationLayer, BlendMode};
+use crate::Transform;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Param {
+    Bool(bool),
+    Float(f32),
+    // set until a transition that checks it is taken
+    Trigger(bool),
+}
+
+impl Param {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Param::Bool(_) => "bool",
+            Param::Float(_) => "float",
+            Param::Trigger(_) => "trigger",
+        }
+    }
+}
+
+// what a transition waits for, a condition on a parameter of another type
+// never holds
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Condition {
+    True(String),
+    False(String),
+    Greater(String, f32),
+    Less(String, f32),
+    Triggered(String),
+    // the clip of the current state played to its end
+    Finished,
+}
+
+impl Condition {
+    fn param(&self) -> Option<&str> {
+        match self {
+            Condition::True(name)
+            | Condition::False(name)
+            | Condition::Greater(name, _)
+            | Condition::Less(name, _)
+            | Condition::Triggered(name) => Some(name),
+            Condition::Finished => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Transition {
+    // none goes from any state but `to`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub to: String,
+    // all of them have to hold
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<Condition>,
+    // of the crossfade, in ms
+    #[serde(default)]
+    pub duration: f32,
+}
+
+impl Transition {
+    pub fn new(from: impl Into<String>, to: impl Into<String>, duration: f32) -> Self {
+        Self {
+            from: Some(from.into()),
+            to: to.into(),
+            conditions: Vec::new(),
+            duration,
+        }
+    }
+
+    pub fn any(to: impl Into<String>, duration: f32) -> Self {
+        Self {
+            from: None,
+            ..Self::new("", to, duration)
+        }
+    }
+
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum StateMachineError {
+    Json(serde_json::Error),
+    UnknownState(String),
+    UnknownParam(String),
+    // set as another type than it was declared with
+    WrongType {
+        name: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for StateMachineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateMachineError::Json(err) => write!(f, "invalid state machine json: {}", err),
+            StateMachineError::UnknownState(name) => write!(f, "no state named {:?}", name),
+            StateMachineError::UnknownParam(name) => write!(f, "no parameter named {:?}", name),
+            StateMachineError::WrongType { name, expected } => {
+                write!(f, "parameter {:?} is a {}", name, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StateMachineError {}
+
+// on an entity that also has an `AnimateComponent<Transform>`, which the
+// machine fills, see `animation_state_machine_sys`. a state whose clip isn't
+// loaded plays nothing and is finished straight away
+#[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationStateMachine {
+    pub initial: String,
+    // state name to clip name
+    pub states: BTreeMap<String, String>,
+    // tried in order, the first one that can be taken is
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+    #[serde(default)]
+    params: BTreeMap<String, Param>,
+    #[serde(skip)]
+    state: Option<String>,
+    // clock time the state was entered at
+    #[serde(skip)]
+    entered: f32,
+    // of the state's clip
+    #[serde(skip)]
+    duration: f32,
+}
+
+impl AnimationStateMachine {
+    pub fn new(initial: impl Into<String>, clip: impl Into<String>) -> Self {
+        let initial = initial.into();
+        Self {
+            states: BTreeMap::from([(initial.clone(), clip.into())]),
+            initial,
+            transitions: Vec::new(),
+            params: BTreeMap::new(),
+            state: None,
+            entered: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    // checks that every state and parameter used exists
+    pub fn from_json(json: &str) -> Result<Self, StateMachineError> {
+        let machine: Self = serde_json::from_str(json).map_err(StateMachineError::Json)?;
+        machine.validate()?;
+        Ok(machine)
+    }
+
+    fn validate(&self) -> Result<(), StateMachineError> {
+        let states = self
+            .transitions
+            .iter()
+            .flat_map(|transition| transition.from.iter().chain([&transition.to]))
+            .chain([&self.initial]);
+        for state in states {
+            if !self.states.contains_key(state) {
+                return Err(StateMachineError::UnknownState(state.clone()));
+            }
+        }
+        let params = self
+            .transitions
+            .iter()
+            .flat_map(|transition| &transition.conditions)
+            .filter_map(Condition::param);
+        for param in params {
+            if !self.params.contains_key(param) {
+                return Err(StateMachineError::UnknownParam(param.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_state(&mut self, name: impl Into<String>, clip: impl Into<String>) -> &mut Self {
+        self.states.insert(name.into(), clip.into());
+        self
+    }
+
+    pub fn add_transition(&mut self, transition: Transition) -> &mut Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    pub fn add_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.params.insert(name.into(), Param::Bool(value));
+        self
+    }
+
+    pub fn add_float(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.params.insert(name.into(), Param::Float(value));
+        self
+    }
+
+    pub fn add_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.params.insert(name.into(), Param::Trigger(false));
+        self
+    }
+
+    pub fn param(&self, name: &str) -> Option<Param> {
+        self.params.get(name).copied()
+    }
+
+    // like `param` with the error `set_param` gives for an undeclared one,
+    // to check a parameter without setting it
+    pub fn lookup_param(&self, name: &str) -> Result<Param, StateMachineError> {
+        self.param(name)
+            .ok_or_else(|| StateMachineError::UnknownParam(name.to_string()))
+    }
+
+    // replaces the value of a declared parameter with one of the same type
+    pub fn set_param(&mut self, name: &str, value: Param) -> Result<(), StateMachineError> {
+        let param = self
+            .params
+            .get_mut(name)
+            .ok_or_else(|| StateMachineError::UnknownParam(name.to_string()))?;
+        if param.kind() != value.kind() {
+            return Err(StateMachineError::WrongType {
+                name: name.to_string(),
+                expected: param.kind(),
+            });
+        }
+        *param = value;
+        Ok(())
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) -> Result<(), StateMachineError> {
+        self.set_param(name, Param::Bool(value))
+    }
+
+    pub fn set_float(&mut self, name: &str, value: f32) -> Result<(), StateMachineError> {
+        self.set_param(name, Param::Float(value))
+    }
+
+    pub fn trigger(&mut self, name: &str) -> Result<(), StateMachineError> {
+        self.set_param(name, Param::Trigger(true))
+    }
+
+    // the state the machine is in, the initial one until it starts
+    pub fn state(&self) -> &str {
+        self.state.as_deref().unwrap_or(&self.initial)
+    }
+
+    fn holds(&self, condition: &Condition, now: f32) -> bool {
+        let param = condition.param().and_then(|name| self.params.get(name));
+        match (condition, param) {
+            (Condition::True(_), Some(Param::Bool(value))) => *value,
+            (Condition::False(_), Some(Param::Bool(value))) => !*value,
+            (Condition::Greater(_, than), Some(Param::Float(value))) => value > than,
+            (Condition::Less(_, than), Some(Param::Float(value))) => value < than,
+            (Condition::Triggered(_), Some(Param::Trigger(set))) => *set,
+            (Condition::Finished, _) => now - self.entered >= self.duration,
+            _ => false,
+        }
+    }
+
+    // the first transition that can be taken at `now`, its triggers are
+    // reset
+    fn take_transition(&mut self, now: f32) -> Option<Transition> {
+        let state = self.state();
+        let transition = self
+            .transitions
+            .iter()
+            .filter(|t| match &t.from {
+                Some(from) => from == state,
+                None => t.to != state,
+            })
+            .find(|t| t.conditions.iter().all(|c| self.holds(c, now)))?
+            .clone();
+        for condition in &transition.conditions {
+            if let Condition::Triggered(name) = condition {
+                self.params.insert(name.clone(), Param::Trigger(false));
+            }
+        }
+        Some(transition)
+    }
+
+    // crossfades `animate` to the clip of `state`
+    fn enter(
+        &mut self,
+        state: String,
+        duration: f32,
+        now: f32,
+        library: &ClipLibrary,
+        animate: &mut AnimateComponent<Transform>,
+    ) {
+        let built = self.states.get(&state).and_then(|clip| library.get(clip));
+        match built.and_then(|clip| clip.build().ok()) {
+            Some(built) => {
+                self.duration = built.total_time();
+                let mut layer = AnimationLayer::new(BlendMode::Override, 1.0);
+                layer.sequences = built.sequences;
+                animate.crossfade(layer, duration);
+            }
+            None => self.duration = 0.0,
+        }
+        self.state = Some(state);
+        self.entered = now;
+    }
+}
+
+// starts every machine in its initial state, then takes at most one
+// transition per machine and frame. runs before the animation systems, on
+// the entity's `AnimationPlayer` time if it has one
+pub fn animation_state_machine_sys(
+    library: Res<ClipLibrary>,
+    mut animation_info: ResMut<AnimationSystemInfo>,
+    mut query: Query<(
+        &mut AnimationStateMachine,
+        &mut AnimateComponent<Transform>,
+        Option<&AnimationPlayer>,
+    )>,
+) {
+    for (mut machine, mut animate, player) in &mut query {
+        let now = player.map_or(animation_info.currentTime, |player| player.time);
+        let (state, duration) = if machine.state.is_none() {
+            (machine.initial.clone(), 0.0)
+        } else {
+            match machine.take_transition(now) {
+                Some(transition) => (transition.to, transition.duration),
+                None => continue,
+            }
+        };
+        machine.enter(state, duration, now, &library, &mut animate);
+        animation_info.needsUpdate = true;
+    }
+}
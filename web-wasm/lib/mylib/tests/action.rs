@@ -0,0 +1,220 @@
+use bevy_ecs::prelude::*;
+use mylib::action::*;
+use mylib::animation::*;
+use mylib::clip::*;
+use mylib::lens::*;
+use mylib::Transform;
+
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+struct Cue {
+    value: f32,
+    calls: u32,
+    flag: bool,
+}
+
+fn value(c: &mut Cue) -> &mut f32 {
+    &mut c.value
+}
+
+fn flag(c: &mut Cue) -> &mut bool {
+    &mut c.flag
+}
+
+fn count() -> Call<Cue> {
+    Call::new(|c: &mut Cue| c.calls += 1)
+}
+
+fn tween(duration: f32) -> Tween<Cue> {
+    Tween::new(EasingFunction::Linear, duration, Lens::new(value, 0.0, 1.0))
+}
+
+fn play(seq: &mut Seq<Cue>, cue: &mut Cue, times: impl IntoIterator<Item = f32>) {
+    for time in times {
+        seq.sample(cue, time);
+    }
+}
+
+#[test]
+fn calls_run_once_when_played_through() {
+    let mut seq = Seq::new();
+    seq.then(count())
+        .then(tween(100.0))
+        .then(count())
+        .then(tween(100.0));
+    let mut cue = Cue::default();
+    play(&mut seq, &mut cue, [0.0, 0.0, 50.0]);
+    assert_eq!(cue.calls, 1);
+    // the frames step right over the second one
+    play(&mut seq, &mut cue, [99.0, 99.0, 150.0, 150.0, 200.0, 300.0]);
+    assert_eq!(cue.calls, 2);
+}
+
+#[test]
+fn calls_run_on_every_cycle() {
+    let mut seq = Seq::new();
+    seq.then(count()).then(tween(100.0)).then(count());
+    seq.repeat(Repeat::Count(2));
+    let mut cue = Cue::default();
+    play(&mut seq, &mut cue, (0..=40).map(|i| i as f32 * 10.0));
+    // two per cycle
+    assert_eq!(cue.calls, 6);
+
+    // going back on the yoyo passes them again
+    let mut seq = Seq::new();
+    seq.then(tween(50.0)).then(count()).then(tween(50.0));
+    seq.yoyo(true).repeat(Repeat::Count(1));
+    let mut cue = Cue::default();
+    play(&mut seq, &mut cue, [0.0, 60.0, 140.0, 200.0]);
+    assert_eq!(cue.calls, 2);
+
+    // a sequence inside a looping one starts over on every cycle
+    let mut inner = Seq::new();
+    inner.then(count()).then(tween(100.0));
+    let mut outer = Seq::new();
+    outer.then(inner).repeat(Repeat::Count(2));
+    let mut cue = Cue::default();
+    play(&mut outer, &mut cue, (0..=30).map(|i| i as f32 * 10.0));
+    assert_eq!(cue.calls, 3);
+}
+
+#[test]
+fn seeks_run_what_they_jump_over() {
+    let mut seq = Seq::new();
+    seq.then(count())
+        .then(tween(100.0))
+        .then(count())
+        .then(tween(100.0));
+    let mut cue = Cue::default();
+    seq.seek(&mut cue, 150.0);
+    assert_eq!(cue.calls, 2);
+    seq.seek(&mut cue, 180.0);
+    play(&mut seq, &mut cue, [190.0]);
+    assert_eq!(cue.calls, 2);
+
+    // back before the second one, which runs again when played through
+    seq.seek(&mut cue, 50.0);
+    assert_eq!(cue.calls, 2);
+    play(&mut seq, &mut cue, [120.0]);
+    assert_eq!(cue.calls, 3);
+
+    // back to the start is a new pass
+    seq.seek(&mut cue, 0.0);
+    play(&mut seq, &mut cue, [0.0, 10.0]);
+    assert_eq!(cue.calls, 4);
+}
+
+#[test]
+fn seeks_run_calls_once_however_many_cycles_they_skip() {
+    let mut seq = Seq::new();
+    seq.then(count()).then(tween(100.0)).then(count());
+    seq.repeat(Repeat::Infinite);
+    let mut cue = Cue::default();
+    seq.seek(&mut cue, 100_050.0);
+    assert_eq!(cue.calls, 2);
+    seq.seek(&mut cue, 200_050.0);
+    assert_eq!(cue.calls, 4);
+    // playing on is back to every pass
+    play(&mut seq, &mut cue, [200_100.0, 200_150.0]);
+    assert_eq!(cue.calls, 6);
+}
+
+#[test]
+fn calls_run_inside_groups() {
+    let mut par = Par::new();
+    par.add(count()).add(tween(100.0)).add_at(50.0, count());
+    let mut cue = Cue::default();
+    for time in [0.0, 0.0, 40.0, 60.0, 100.0] {
+        par.sample(&mut cue, time);
+    }
+    assert_eq!(cue.calls, 2);
+    // back over the second one, then a seek forward over it
+    par.sample(&mut cue, 20.0);
+    assert_eq!(cue.calls, 3);
+    par.seek(&mut cue, 80.0);
+    assert_eq!(cue.calls, 4);
+
+    let mut seq = Seq::new();
+    seq.then(Stagger::repeat(30.0, 3, count()))
+        .then(tween(100.0));
+    let mut cue = Cue::default();
+    play(&mut seq, &mut cue, (0..=20).map(|i| i as f32 * 10.0));
+    assert_eq!(cue.calls, 3);
+}
+
+#[test]
+fn sets_put_back_what_was_there() {
+    let mut seq = Seq::new();
+    seq.then_delay(100.0)
+        .then(Set::new(flag, true))
+        .then(Set::new(value, 5.0));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+
+    let mut cue = Cue {
+        value: 2.0,
+        ..Cue::default()
+    };
+    animate.capture(&cue);
+    animate.seek(&mut cue, 100.0);
+    assert!(cue.flag);
+    assert_eq!(cue.value, 5.0);
+    animate.seek(&mut cue, 50.0);
+    assert!(!cue.flag);
+    assert_eq!(cue.value, 2.0);
+
+    animate.reset(&mut cue);
+    cue.value = 3.0;
+    animate.seek(&mut cue, 100.0);
+    animate.seek(&mut cue, 0.0);
+    assert_eq!(cue.value, 3.0);
+}
+
+#[test]
+fn calls_complete_like_tweens() {
+    let mut world = World::new();
+    world.insert_resource(AnimationSystemInfo::default());
+    let mut seq = Seq::new();
+    seq.then(tween(100.0)).then(count().with_tag(7));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    world.spawn((Cue::default(), animate));
+
+    let mut schedule = Schedule::new();
+    add_animation_events(&mut world, &mut schedule);
+    schedule.add_system(animation_sys::<Cue>);
+    for time in [0.0, 50.0, 150.0] {
+        world.resource_mut::<AnimationSystemInfo>().currentTime = time;
+        schedule.run(&mut world);
+    }
+    let tags: Vec<u32> = world
+        .resource_mut::<Events<TweenCompleted>>()
+        .drain()
+        .map(|event| event.user_tag)
+        .collect();
+    assert_eq!(tags, vec![0, 7]);
+    assert_eq!(world.query::<&Cue>().single(&world).calls, 1);
+}
+
+#[test]
+fn clips_call_js_systems() {
+    let clip = Clip::from_ron(
+        r#"(
+            name: "steps",
+            sequences: [(steps: [Delay(100), Call((name: "footstep_sys", tag: 2))])],
+        )"#,
+    )
+    .unwrap();
+    assert_eq!(
+        clip.sequences[0].steps[1],
+        StepDef::Call(CallDef {
+            name: "footstep_sys".into(),
+            tag: 2,
+        })
+    );
+    assert_eq!(Clip::from_ron(&clip.to_ron().unwrap()).unwrap(), clip);
+
+    // queued until the world is released, nothing registered, nothing runs
+    let mut animate = clip.build().unwrap();
+    animate.seek(&mut Transform::default(), 200.0);
+    mylib::run_js_calls();
+}
@@ -0,0 +1,133 @@
+use mylib::animation::*;
+use mylib::clip::*;
+use mylib::Transform;
+
+const JSON: &str = r#"{
+  "name": "hop",
+  "sequences": [
+    {
+      "steps": [
+        {
+          "Tween": {
+            "duration": 100.0,
+            "ease": "QuadraticIn",
+            "target": { "PositionX": { "from": 0.0, "to": 5.0 } },
+            "tag": 3
+          }
+        },
+        { "Delay": 50.0 },
+        {
+          "Par": [
+            {
+              "step": {
+                "Tween": {
+                  "duration": 100.0,
+                  "ease": { "CubicBezier": [0.25, 0.1, 0.25, 1.0] },
+                  "target": {
+                    "Scale": {
+                      "from": { "x": 1.0, "y": 1.0, "z": 1.0 },
+                      "to": { "x": 2.0, "y": 2.0, "z": 2.0 }
+                    }
+                  }
+                }
+              }
+            },
+            {
+              "offset": 20.0,
+              "step": {
+                "Seq": {
+                  "steps": [
+                    {
+                      "Tween": {
+                        "duration": 80.0,
+                        "ease": { "Steps": [4, "JumpEnd"] },
+                        "target": { "PositionY": { "from": 0.0, "to": 1.0 } }
+                      }
+                    }
+                  ],
+                  "repeat": { "Count": 1 },
+                  "yoyo": true
+                }
+              }
+            }
+          ]
+        }
+      ],
+      "repeat": { "Count": 2 },
+      "repeat_delay": 10.0
+    }
+  ]
+}"#;
+
+#[test]
+fn json_round_trips() {
+    let clip = Clip::from_json(JSON).unwrap();
+    let written: serde_json::Value = serde_json::from_str(&clip.to_json().unwrap()).unwrap();
+    let original: serde_json::Value = serde_json::from_str(JSON).unwrap();
+    assert_eq!(written, original);
+}
+
+#[test]
+fn ron_round_trips() {
+    let clip = Clip::from_json(JSON).unwrap();
+    let ron = clip.to_ron().unwrap();
+    assert_eq!(Clip::from_ron(&ron).unwrap(), clip);
+    assert_eq!(Clip::from_ron(&ron).unwrap().to_ron().unwrap(), ron);
+}
+
+#[test]
+fn ron_reads_like_rust() {
+    let clip = Clip::from_ron(
+        r#"(
+            name: "bounce",
+            sequences: [(
+                steps: [
+                    Tween((duration: 500, ease: QuadraticOut, target: PositionY(from: 0, to: 2))),
+                    Delay(100),
+                ],
+                repeat: Infinite,
+                yoyo: true,
+            )],
+        )"#,
+    )
+    .unwrap();
+    assert_eq!(clip.name, "bounce");
+    assert_eq!(clip.sequences[0].repeat, Repeat::Infinite);
+    assert_eq!(
+        clip.sequences[0].steps[0],
+        StepDef::Tween(TweenDef {
+            duration: 500.0,
+            ease: EasingFunction::QuadraticOut,
+            target: TransformProperty::PositionY {
+                from: Some(0.0),
+                to: 2.0
+            },
+            tag: 0,
+            relative: None,
+        })
+    );
+}
+
+#[test]
+fn built_clips_play_like_code() {
+    let mut animate = Clip::from_json(JSON).unwrap().build().unwrap();
+    // 3 cycles of 100 + 50 + 20 + 2 * 80 and 2 repeat delays
+    assert_eq!(animate.total_time(), 1010.0);
+
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 50.0);
+    assert_eq!(transform.position.x, 5.0 * 0.25);
+    animate.seek(&mut transform, 200.0);
+    assert_eq!(transform.position.x, 5.0);
+    assert!(transform.scale.x > 1.0 && transform.scale.x < 2.0);
+    // second step of the 4 steps
+    assert_eq!(transform.position.y, 0.25);
+}
+
+#[test]
+fn bad_clips_are_errors() {
+    let err = Clip::from_json(r#"{ "name": "x", "sequences": [{ "steps": [{ "Wait": 1 }] }] }"#)
+        .unwrap_err();
+    assert!(err.to_string().starts_with("invalid clip json"), "{}", err);
+    assert!(Clip::from_ron("(name: 1)").is_err());
+}
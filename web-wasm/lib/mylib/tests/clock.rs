@@ -0,0 +1,56 @@
+mod common;
+
+use common::{value, Harness, Value};
+use mylib::animation::*;
+use mylib::lens::*;
+
+fn animate(duration: f32) -> AnimateComponent<Value> {
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(Tween::new(
+        EasingFunction::Linear,
+        duration,
+        Lens::new(value, 0.0, 1.0),
+    ));
+    animate
+}
+
+// the clock after `frames` more frames
+fn run(harness: &mut Harness<Value>, frames: usize) -> (f32, f32) {
+    harness.step(frames);
+    let info = harness.info();
+    (info.currentTime, info.totalTime)
+}
+
+#[test]
+fn animations_spawned_later_extend_the_clock() {
+    let mut harness = Harness::new(100.0);
+    harness.spawn("early", Value(0.0), animate(200.0));
+    assert_eq!(run(&mut harness, 5), (200.0, 200.0));
+
+    // on the global timeline too, without the new total it would stay cut
+    // where the clock stopped
+    let late = harness.spawn("late", Value(0.0), animate(400.0));
+    assert_eq!(run(&mut harness, 1), (200.0, 400.0));
+    assert_eq!(harness.target(late).0, 0.5);
+    assert_eq!(run(&mut harness, 5), (400.0, 400.0));
+    assert_eq!(harness.target(late).0, 1.0);
+}
+
+#[test]
+fn changed_animations_are_measured_again() {
+    let mut harness = Harness::new(100.0);
+    let entity = harness.spawn("value", Value(0.0), animate(200.0));
+    assert_eq!(run(&mut harness, 2), (100.0, 200.0));
+
+    let mut seq = Seq::new();
+    seq.then_delay(600.0);
+    harness
+        .world
+        .get_mut::<AnimateComponent<Value>>(entity)
+        .unwrap()
+        .add_seq(seq);
+    assert_eq!(run(&mut harness, 1).1, 600.0);
+    // playing alone never does
+    assert!(!harness.info().needsUpdate);
+    assert_eq!(run(&mut harness, 10), (600.0, 600.0));
+}
@@ -0,0 +1,184 @@
+// runs animations headless on a fixed clock and checks what they write,
+// frame by frame, against golden files in `tests/golden`. set
+// `UPDATE_GOLDEN=1` to write the files instead of checking them. also the
+// components most tests animate. each test crate uses its own part of this
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::plugin::{AnimationPlugin, Plugin};
+use serde::Serialize;
+use serde_json::Value as Json;
+
+#[derive(Component, Resource, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Value(pub f32);
+
+pub fn value(v: &mut Value) -> &mut f32 {
+    &mut v.0
+}
+
+// two fields, for children that write different ones
+#[derive(Component, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Pair(pub f32, pub f32);
+
+pub fn first(p: &mut Pair) -> &mut f32 {
+    &mut p.0
+}
+
+pub fn second(p: &mut Pair) -> &mut f32 {
+    &mut p.1
+}
+
+// `get` from 0 to 1 over `duration` ms
+pub fn linear<T: 'static>(duration: f32, get: fn(&mut T) -> &mut f32) -> Tween<T> {
+    Tween::new(EasingFunction::Linear, duration, Lens::new(get, 0.0, 1.0))
+}
+
+// numbers this close match, floats go through text and easings through libm
+const TOLERANCE: f64 = 1e-4;
+
+#[derive(Serialize)]
+pub struct Frame {
+    pub time: f32,
+    // named entity to its component
+    pub values: BTreeMap<String, Json>,
+}
+
+pub struct Harness<T> {
+    pub world: World,
+    pub schedule: Schedule,
+    entities: Vec<(String, Entity)>,
+    frames: Vec<Frame>,
+    _target: std::marker::PhantomData<T>,
+}
+
+impl<T: Component + Clone + Debug + Serialize> Harness<T> {
+    // every frame after the first moves the clock by `dt` ms
+    pub fn new(dt: f32) -> Self {
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        AnimationPlugin::<T>::new().build(&mut world, &mut schedule);
+        world.resource_mut::<AnimationSystemInfo>().fixedDt = Some(dt);
+        Self {
+            world,
+            schedule,
+            entities: Vec::new(),
+            frames: Vec::new(),
+            _target: std::marker::PhantomData,
+        }
+    }
+
+    // recorded under `name`
+    pub fn spawn(
+        &mut self,
+        name: impl Into<String>,
+        target: T,
+        animate: AnimateComponent<T>,
+    ) -> Entity {
+        let entity = self.world.spawn((target, animate)).id();
+        self.entities.push((name.into(), entity));
+        entity
+    }
+
+    pub fn target(&self, entity: Entity) -> &T {
+        self.world.get::<T>(entity).unwrap()
+    }
+
+    pub fn info(&mut self) -> Mut<'_, AnimationSystemInfo> {
+        self.world.resource_mut::<AnimationSystemInfo>()
+    }
+
+    // runs `frames` frames and records each of them
+    pub fn step(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.schedule.run(&mut self.world);
+            let values = self
+                .entities
+                .iter()
+                .map(|(name, entity)| {
+                    let target = self.world.get::<T>(*entity).unwrap();
+                    (name.clone(), serde_json::to_value(target).unwrap())
+                })
+                .collect();
+            let time = self.world.resource::<AnimationSystemInfo>().currentTime;
+            self.frames.push(Frame { time, values });
+        }
+        self
+    }
+
+    // compares everything recorded with `tests/golden/<name>.json`
+    pub fn assert_golden(&self, name: &str) {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", name]
+            .iter()
+            .collect::<PathBuf>()
+            .with_extension("json");
+        // one frame per line keeps the diffs readable
+        let lines: Vec<String> = self
+            .frames
+            .iter()
+            .map(|frame| serde_json::to_string(frame).unwrap())
+            .collect();
+        let recorded = format!("[\n{}\n]\n", lines.join(",\n"));
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, recorded).unwrap();
+            return;
+        }
+        let golden = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "no golden file at {}, run with UPDATE_GOLDEN=1 to write it",
+                path.display()
+            )
+        });
+        let golden: Vec<Json> = serde_json::from_str(&golden).unwrap();
+        let recorded: Vec<Json> = serde_json::from_str(&recorded).unwrap();
+        assert_eq!(
+            golden.len(),
+            recorded.len(),
+            "{}: frame count differs",
+            name
+        );
+        for (index, (expected, actual)) in golden.iter().zip(&recorded).enumerate() {
+            if let Err(at) = matches(expected, actual, String::new()) {
+                panic!(
+                    "{}: frame {} differs at {}\nexpected {}\n     got {}",
+                    name, index, at, expected, actual
+                );
+            }
+        }
+    }
+}
+
+// the path of the first difference
+fn matches(expected: &Json, actual: &Json, path: String) -> Result<(), String> {
+    match (expected, actual) {
+        (Json::Number(a), Json::Number(b)) => {
+            let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+            if (a - b).abs() <= TOLERANCE {
+                Ok(())
+            } else {
+                Err(path)
+            }
+        }
+        (Json::Array(a), Json::Array(b)) if a.len() == b.len() => a
+            .iter()
+            .zip(b)
+            .enumerate()
+            .try_for_each(|(i, (a, b))| matches(a, b, format!("{}[{}]", path, i))),
+        (Json::Object(a), Json::Object(b)) if a.len() == b.len() => {
+            a.iter().try_for_each(|(key, a)| {
+                let b = b.get(key).ok_or_else(|| format!("{}.{}", path, key))?;
+                matches(a, b, format!("{}.{}", path, key))
+            })
+        }
+        (a, b) if a == b => Ok(()),
+        _ => Err(path),
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use common::{first, second, Pair};
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::{Transform, Vector3};
+
+fn tween(duration: f32, get: fn(&mut Pair) -> &mut f32, to: f32) -> Tween<Pair> {
+    Tween::new(EasingFunction::SineInOut, duration, Lens::new(get, 0.0, to))
+}
+
+#[test]
+fn long_sequences_play_like_they_seek() {
+    let mut seq = Seq::new();
+    for key in 0..500 {
+        let get = if key % 2 == 0 { first } else { second };
+        seq.then(tween(7.0 + (key % 5) as f32, get, key as f32));
+        if key % 50 == 0 {
+            // overlaps whatever comes next
+            seq.insert("<", tween(120.0, second, -1.0));
+        }
+    }
+    seq.repeat(Repeat::Count(1)).yoyo(true);
+
+    let mut played = Pair(0.0, 0.0);
+    let mut time = 0.0;
+    while time <= seq.duration() + 20.0 {
+        seq.sample(&mut played, time);
+        let mut sought = Pair(0.0, 0.0);
+        seq.clone().seek(&mut sought, time);
+        assert_eq!(played, sought, "at {}", time);
+        time += 13.0;
+    }
+}
+
+#[test]
+fn a_child_as_long_as_the_sequence_plays_along_the_short_ones() {
+    let mut seq = Seq::new();
+    for key in 0..200 {
+        seq.then(tween(10.0, first, key as f32));
+    }
+    seq.insert(0.0, tween(seq.duration(), second, 1.0));
+
+    let mut played = Pair(0.0, 0.0);
+    let mut time = 0.0;
+    while time <= seq.duration() + 20.0 {
+        seq.sample(&mut played, time);
+        let mut sought = Pair(0.0, 0.0);
+        seq.clone().seek(&mut sought, time);
+        assert_eq!(played, sought, "at {}", time);
+        time += 7.0;
+    }
+    assert_eq!(played, Pair(199.0, 1.0));
+}
+
+#[test]
+fn playing_back_rewrites_what_finished_children_wrote() {
+    let linear = EasingFunction::Linear;
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        linear,
+        10.0,
+        Lens::position(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 2.0, 3.0)),
+    ))
+    .then(Tween::new(linear, 10.0, Lens::position_y(0.0, 5.0)))
+    .then(Tween::new(
+        linear,
+        1000.0,
+        Lens::scale(Vector3::new(1.0, 1.0, 1.0), Vector3::new(2.0, 2.0, 2.0)),
+    ))
+    // puts `x` back to 0 when played back past its start, the first tween
+    // wrote it long before
+    .then(Tween::new(linear, 10.0, Lens::position_x(0.0, 9.0)));
+    seq.repeat(Repeat::Count(1)).yoyo(true);
+
+    let mut played = Transform::default();
+    let mut time = 0.0;
+    while time <= seq.duration() + 20.0 {
+        seq.sample(&mut played, time);
+        let mut sought = Transform::default();
+        seq.clone().seek(&mut sought, time);
+        assert_eq!(played.position, sought.position, "at {}", time);
+        assert_eq!(played.scale, sought.scale, "at {}", time);
+        time += 7.0;
+    }
+    assert_eq!(played.position, Vector3::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn changes_through_tweens_are_picked_up() {
+    let mut seq = Seq::new();
+    seq.then(tween(100.0, first, 1.0))
+        .then(tween(100.0, second, 1.0));
+    let mut v = Pair(0.0, 0.0);
+    seq.sample(&mut v, 0.0);
+    seq.sample(&mut v, 150.0);
+    assert_eq!(v.0, 1.0);
+
+    // the first tween runs again, after the playhead went past it
+    seq.tweens_mut()[0]
+        .downcast_mut::<Tween<Pair>>()
+        .unwrap()
+        .duration = 400.0;
+    assert_eq!(seq.duration(), 400.0);
+    seq.sample(&mut v, 200.0);
+    assert_eq!(v.0, 0.5);
+    assert_eq!(v.1, 1.0);
+}
@@ -0,0 +1,193 @@
+use mylib::animation::{easing_function_to_ratio, EasingFunction, EasingFunction::*, StepPosition};
+
+const EPSILON: f32 = 1e-4;
+
+fn assert_ratio(func: EasingFunction, val: f32, expected: f32) {
+    let actual = easing_function_to_ratio(func, val);
+    assert!(
+        (actual - expected).abs() < EPSILON,
+        "{:?}({}) = {}, expected {}",
+        func,
+        val,
+        actual,
+        expected
+    );
+}
+
+const PENNER: [EasingFunction; 31] = [
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuarticIn,
+    QuarticOut,
+    QuarticInOut,
+    QuinticIn,
+    QuinticOut,
+    QuinticInOut,
+    ExponentialIn,
+    ExponentialOut,
+    ExponentialInOut,
+    CircularIn,
+    CircularOut,
+    CircularInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+];
+
+#[test]
+fn every_curve_starts_at_zero_and_ends_at_one() {
+    for func in PENNER {
+        assert_ratio(func, 0.0, 0.0);
+        assert_ratio(func, 1.0, 1.0);
+    }
+}
+
+#[test]
+fn input_is_clamped() {
+    for func in PENNER {
+        assert_ratio(func, -0.5, 0.0);
+        assert_ratio(func, 1.5, 1.0);
+    }
+}
+
+#[test]
+fn in_out_variants_are_symmetric() {
+    let pairs = [
+        SineInOut,
+        QuadraticInOut,
+        CubicInOut,
+        QuarticInOut,
+        QuinticInOut,
+        ExponentialInOut,
+        CircularInOut,
+        BackInOut,
+        ElasticInOut,
+        BounceInOut,
+    ];
+    for func in pairs {
+        assert_ratio(func, 0.5, 0.5);
+        for i in 1..10 {
+            let p = i as f32 / 20.0;
+            let a = easing_function_to_ratio(func, p);
+            let b = easing_function_to_ratio(func, 1.0 - p);
+            assert!(
+                (a + b - 1.0).abs() < EPSILON,
+                "{:?} is not symmetric at {}",
+                func,
+                p
+            );
+        }
+    }
+}
+
+#[test]
+fn penner_reference_values() {
+    // reference values taken from the formulas on easings.net, the bounce
+    // family uses the piecewise quadratic from the original implementation
+    let cases = [
+        (Linear, 0.3, 0.3),
+        (SineIn, 0.5, 0.292_893_2),
+        (SineOut, 0.5, std::f32::consts::FRAC_1_SQRT_2),
+        (SineInOut, 0.25, 0.146_446_6),
+        (QuadraticIn, 0.5, 0.25),
+        (QuadraticOut, 0.5, 0.75),
+        (QuadraticInOut, 0.25, 0.125),
+        (QuadraticInOut, 0.75, 0.875),
+        (CubicIn, 0.5, 0.125),
+        (CubicOut, 0.5, 0.875),
+        (CubicInOut, 0.25, 0.0625),
+        (QuarticIn, 0.5, 0.0625),
+        (QuarticOut, 0.5, 0.9375),
+        (QuarticInOut, 0.25, 0.031_25),
+        (QuinticIn, 0.5, 0.031_25),
+        (QuinticOut, 0.5, 0.968_75),
+        (QuinticInOut, 0.25, 0.015_625),
+        (ExponentialIn, 0.5, 0.031_25),
+        (ExponentialOut, 0.5, 0.968_75),
+        (ExponentialInOut, 0.25, 0.015_625),
+        (CircularIn, 0.5, 0.133_974_6),
+        (CircularOut, 0.5, 0.866_025_4),
+        (CircularInOut, 0.25, 0.066_987_3),
+        (BackIn, 0.5, -0.087_697_5),
+        (BackOut, 0.5, 1.087_697_5),
+        (BackInOut, 0.25, -0.099_681_8),
+        (ElasticIn, 0.5, -0.015_625),
+        (ElasticOut, 0.5, 1.015_625),
+        (ElasticInOut, 0.25, 0.011_969_4),
+        (BounceOut, 0.2, 0.3025),
+        (BounceOut, 0.5, 0.718_75),
+        (BounceOut, 0.8, 0.912_243_8),
+        (BounceOut, 0.95, 0.973),
+        (BounceIn, 0.5, 0.281_25),
+        (BounceInOut, 0.25, 0.140_625),
+    ];
+    for (func, val, expected) in cases {
+        assert_ratio(func, val, expected);
+    }
+}
+
+#[test]
+fn cubic_bezier_matches_css_keywords() {
+    // `linear`
+    for i in 0..=10 {
+        let p = i as f32 / 10.0;
+        assert_ratio(CubicBezier(0.0, 0.0, 1.0, 1.0), p, p);
+    }
+
+    // `ease`
+    assert_ratio(CubicBezier(0.25, 0.1, 0.25, 1.0), 0.25, 0.408_510_6);
+    assert_ratio(CubicBezier(0.25, 0.1, 0.25, 1.0), 0.5, 0.802_403_4);
+    // `ease-in`
+    assert_ratio(CubicBezier(0.42, 0.0, 1.0, 1.0), 0.5, 0.315_356_1);
+    // `ease-out`
+    assert_ratio(CubicBezier(0.0, 0.0, 0.58, 1.0), 0.5, 0.684_643_9);
+    // `ease-in-out`
+    assert_ratio(CubicBezier(0.42, 0.0, 0.58, 1.0), 0.5, 0.5);
+
+    // y values outside of [0, 1] overshoot
+    assert!(easing_function_to_ratio(CubicBezier(0.3, -0.5, 0.7, 1.5), 0.1) < 0.0);
+    assert!(easing_function_to_ratio(CubicBezier(0.3, -0.5, 0.7, 1.5), 0.9) > 1.0);
+}
+
+#[test]
+fn steps_follow_css_jump_terms() {
+    let jump_end = Steps(4, StepPosition::JumpEnd);
+    assert_ratio(jump_end, 0.0, 0.0);
+    assert_ratio(jump_end, 0.24, 0.0);
+    assert_ratio(jump_end, 0.25, 0.25);
+    assert_ratio(jump_end, 0.99, 0.75);
+    assert_ratio(jump_end, 1.0, 1.0);
+
+    let jump_start = Steps(4, StepPosition::JumpStart);
+    assert_ratio(jump_start, 0.0, 0.25);
+    assert_ratio(jump_start, 0.5, 0.75);
+    assert_ratio(jump_start, 0.99, 1.0);
+    assert_ratio(jump_start, 1.0, 1.0);
+
+    let jump_none = Steps(5, StepPosition::JumpNone);
+    assert_ratio(jump_none, 0.0, 0.0);
+    assert_ratio(jump_none, 0.2, 0.25);
+    assert_ratio(jump_none, 0.5, 0.5);
+    assert_ratio(jump_none, 0.99, 1.0);
+    assert_ratio(jump_none, 1.0, 1.0);
+
+    let jump_both = Steps(3, StepPosition::JumpBoth);
+    assert_ratio(jump_both, 0.0, 0.25);
+    assert_ratio(jump_both, 0.34, 0.5);
+    assert_ratio(jump_both, 0.67, 0.75);
+    assert_ratio(jump_both, 1.0, 1.0);
+}
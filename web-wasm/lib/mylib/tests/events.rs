@@ -0,0 +1,128 @@
+mod common;
+
+use bevy_ecs::prelude::*;
+use common::{Harness, Value};
+use mylib::animation::*;
+
+#[derive(Clone, Debug)]
+struct ValueLens {
+    start: f32,
+    end: f32,
+}
+
+impl TweenTarget<Value> for ValueLens {
+    fn lerp(&mut self, target: &mut Value, ratio: f32) {
+        target.0 = self.start + (self.end - self.start) * ratio;
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Tween(usize, u32),
+    Sequence(usize),
+    Looped(usize, u32),
+}
+
+// the clock stays where `frame` puts it
+fn harness(animate: AnimateComponent<Value>) -> (Harness<Value>, Entity) {
+    let mut harness = Harness::new(0.0);
+    let entity = harness.spawn("value", Value(0.0), animate);
+    (harness, entity)
+}
+
+// runs a frame at `time` and returns everything it sent
+fn frame(harness: &mut Harness<Value>, entity: Entity, time: f32) -> Vec<Event> {
+    harness.info().currentTime = time;
+    harness.step(1);
+
+    let world = &mut harness.world;
+    let mut events = Vec::new();
+    for event in world.resource_mut::<Events<TweenCompleted>>().drain() {
+        assert_eq!(event.entity, entity);
+        events.push(Event::Tween(event.tween_index, event.user_tag));
+    }
+    for event in world.resource_mut::<Events<AnimationLooped>>().drain() {
+        assert_eq!(event.entity, entity);
+        events.push(Event::Looped(event.seq_index, event.cycle));
+    }
+    for event in world.resource_mut::<Events<SequenceCompleted>>().drain() {
+        assert_eq!(event.entity, entity);
+        events.push(Event::Sequence(event.seq_index));
+    }
+    events
+}
+
+fn tween(start: f32, end: f32, tag: u32) -> Tween<Value> {
+    Tween::new(EasingFunction::Linear, 100.0, ValueLens { start, end }).with_tag(tag)
+}
+
+#[test]
+fn tweens_and_sequences_complete_once() {
+    let mut seq = Seq::new();
+    seq.then(tween(0.0, 1.0, 1))
+        .then_delay(50.0)
+        .then(tween(1.0, 2.0, 2));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    let (mut harness, entity) = harness(animate);
+
+    assert_eq!(frame(&mut harness, entity, 0.0), vec![]);
+    assert_eq!(frame(&mut harness, entity, 99.0), vec![]);
+    assert_eq!(frame(&mut harness, entity, 100.0), vec![Event::Tween(0, 1)]);
+    assert_eq!(frame(&mut harness, entity, 100.0), vec![]);
+    // several in a single frame, in order
+    assert_eq!(
+        frame(&mut harness, entity, 260.0),
+        vec![Event::Tween(1, 0), Event::Tween(2, 2), Event::Sequence(0)]
+    );
+    assert_eq!(frame(&mut harness, entity, 300.0), vec![]);
+}
+
+#[test]
+fn yoyo_cycles_loop_and_complete_backwards() {
+    let mut seq = Seq::new();
+    seq.then(tween(0.0, 1.0, 1))
+        .then_delay(50.0)
+        .then(tween(1.0, 2.0, 2));
+    seq.repeat(Repeat::Count(1)).yoyo(true);
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    let (mut harness, entity) = harness(animate);
+
+    assert_eq!(frame(&mut harness, entity, 0.0), vec![]);
+    assert_eq!(frame(&mut harness, entity, 120.0), vec![Event::Tween(0, 1)]);
+    assert_eq!(
+        frame(&mut harness, entity, 260.0),
+        vec![Event::Tween(1, 0), Event::Tween(2, 2), Event::Looped(0, 1)]
+    );
+    // the second cycle plays the children backwards, last to first
+    assert_eq!(
+        frame(&mut harness, entity, 500.0),
+        vec![
+            Event::Tween(2, 2),
+            Event::Tween(1, 0),
+            Event::Tween(0, 1),
+            Event::Sequence(0)
+        ]
+    );
+}
+
+#[test]
+fn seeking_sends_nothing() {
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(tween(0.0, 1.0, 7));
+    let (mut harness, entity) = harness(animate);
+
+    assert_eq!(frame(&mut harness, entity, 0.0), vec![]);
+    harness.info().seek(150.0);
+    harness.step(1);
+    assert_eq!(frame(&mut harness, entity, 150.0), vec![]);
+    assert_eq!(harness.target(entity), &Value(1.0));
+
+    // playing back over the end completes it backwards
+    assert_eq!(frame(&mut harness, entity, 50.0), vec![]);
+    assert_eq!(
+        frame(&mut harness, entity, 0.0),
+        vec![Event::Tween(0, 7), Event::Sequence(0)]
+    );
+}
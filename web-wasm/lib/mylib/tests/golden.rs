@@ -0,0 +1,119 @@
+mod common;
+
+use bevy_ecs::prelude::*;
+use common::{value, Harness, Value};
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::{Quat, Transform, Vector3};
+
+const EASINGS: [EasingFunction; 33] = [
+    EasingFunction::Linear,
+    EasingFunction::SineIn,
+    EasingFunction::SineOut,
+    EasingFunction::SineInOut,
+    EasingFunction::QuadraticIn,
+    EasingFunction::QuadraticOut,
+    EasingFunction::QuadraticInOut,
+    EasingFunction::CubicIn,
+    EasingFunction::CubicOut,
+    EasingFunction::CubicInOut,
+    EasingFunction::QuarticIn,
+    EasingFunction::QuarticOut,
+    EasingFunction::QuarticInOut,
+    EasingFunction::QuinticIn,
+    EasingFunction::QuinticOut,
+    EasingFunction::QuinticInOut,
+    EasingFunction::ExponentialIn,
+    EasingFunction::ExponentialOut,
+    EasingFunction::ExponentialInOut,
+    EasingFunction::CircularIn,
+    EasingFunction::CircularOut,
+    EasingFunction::CircularInOut,
+    EasingFunction::BackIn,
+    EasingFunction::BackOut,
+    EasingFunction::BackInOut,
+    EasingFunction::ElasticIn,
+    EasingFunction::ElasticOut,
+    EasingFunction::ElasticInOut,
+    EasingFunction::BounceIn,
+    EasingFunction::BounceOut,
+    EasingFunction::BounceInOut,
+    EasingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0),
+    EasingFunction::Steps(4, StepPosition::JumpEnd),
+];
+
+#[test]
+fn easings() {
+    let mut harness = Harness::new(25.0);
+    for ease in EASINGS {
+        let mut animate = AnimateComponent::new();
+        animate.add_tween(Tween::new(ease, 500.0, Lens::new(value, 0.0, 1.0)));
+        harness.spawn(format!("{:?}", ease), Value(0.0), animate);
+    }
+    // and one frame past the end
+    harness.step(22).assert_golden("easings");
+}
+
+#[test]
+fn seeks_reverses_and_resets() {
+    let mut harness = Harness::new(40.0);
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::QuadraticInOut,
+        400.0,
+        Lens::position(Vector3::ZERO, Vector3::new(4.0, 2.0, 0.0)),
+    ))
+    .then_delay(100.0)
+    .then(Tween::new(
+        EasingFunction::SineOut,
+        300.0,
+        Lens::rotation(
+            Quat::IDENTITY,
+            Quat::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 1.5),
+        ),
+    ));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    harness.spawn("transform", Transform::default(), animate);
+
+    harness.step(6);
+    harness.info().seek(650.0);
+    harness.step(3);
+    harness.info().timeScale = -1.0;
+    harness.step(10);
+    harness.info().seek(200.0);
+    harness.step(2);
+    harness.info().reset();
+    harness.step(2);
+    harness.assert_golden("seek");
+}
+
+#[test]
+fn repeats_and_yoyos() {
+    let mut harness = Harness::new(50.0);
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::CubicIn,
+        200.0,
+        Lens::new(value, 0.0, 10.0),
+    ))
+    .repeat(Repeat::Count(2))
+    .yoyo(true)
+    .repeat_delay(50.0);
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    harness.spawn("yoyo", Value(0.0), animate);
+
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::Linear,
+        150.0,
+        Lens::new(value, 0.0, 1.0),
+    ))
+    .repeat(Repeat::Count(3));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    harness.spawn("repeat", Value(0.0), animate);
+
+    harness.step(18).assert_golden("repeat");
+}
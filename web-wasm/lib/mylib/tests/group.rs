@@ -0,0 +1,48 @@
+mod common;
+
+use common::{first, linear, second, Pair};
+use mylib::animation::*;
+
+#[test]
+fn a_par_lasts_as_long_as_its_longest_child() {
+    let mut par = Par::new();
+    par.add(linear(100.0, first))
+        .add_at(50.0, linear(200.0, second));
+    assert_eq!(par.duration(), 250.0);
+
+    let mut seq = Seq::new();
+    seq.then(par).then(linear(100.0, first));
+    assert_eq!(seq.tweens()[1].start_abs(), 250.0);
+
+    let mut v = Pair(0.0, 0.0);
+    seq.sample(&mut v, 0.0);
+    // both children run at once
+    seq.sample(&mut v, 75.0);
+    assert_eq!(v, Pair(0.75, 0.125));
+    seq.sample(&mut v, 150.0);
+    assert_eq!(v, Pair(1.0, 0.5));
+    // the tween after the group starts once the longest child ended
+    seq.sample(&mut v, 300.0);
+    assert_eq!(v, Pair(0.5, 1.0));
+}
+
+#[test]
+fn a_stagger_offsets_each_child_by_the_same_step() {
+    let stagger = Stagger::repeat(40.0, 4, linear(100.0, first));
+    let starts: Vec<f32> = stagger
+        .children()
+        .iter()
+        .map(|child| child.start_abs())
+        .collect();
+    assert_eq!(starts, [0.0, 40.0, 80.0, 120.0]);
+    assert_eq!(stagger.duration(), 220.0);
+
+    let mut stagger = Stagger::new(50.0);
+    stagger.add(linear(100.0, first)).add(linear(100.0, second));
+    let mut seq = Seq::new();
+    seq.then_delay(30.0).then(stagger);
+    let mut v = Pair(0.0, 0.0);
+    seq.sample(&mut v, 0.0);
+    seq.sample(&mut v, 130.0);
+    assert_eq!(v, Pair(1.0, 0.5));
+}
@@ -0,0 +1,90 @@
+use bevy_ecs::prelude::Component;
+use mylib::animation::*;
+use mylib::keyframe::*;
+use mylib::{Transform, Vector3};
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Value(f32);
+
+fn track() -> KeyframeTrack<Value, f32> {
+    KeyframeTrack::new(|v: &mut Value| &mut v.0)
+}
+
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+}
+
+#[test]
+fn step_and_linear_segments() {
+    let mut track = track();
+    track
+        .key(100.0, 4.0, Interpolation::Linear)
+        .key(0.0, 0.0, Interpolation::Step)
+        .key(200.0, 0.0, Interpolation::Linear);
+
+    // sorted whatever the insertion order
+    let times: Vec<f32> = track.keys().iter().map(|k| k.time).collect();
+    assert_eq!(times, vec![0.0, 100.0, 200.0]);
+
+    assert_eq!(track.value_at(-10.0), Some(0.0));
+    assert_eq!(track.value_at(99.0), Some(0.0));
+    assert_eq!(track.value_at(100.0), Some(4.0));
+    assert_eq!(track.value_at(150.0), Some(2.0));
+    assert_eq!(track.value_at(300.0), Some(0.0));
+    assert_eq!(self::track().value_at(0.0), None);
+}
+
+#[test]
+fn hermite_segments_follow_their_tangents() {
+    let mut track = track();
+    // tangents in value per ms
+    track
+        .hermite_key(0.0, 0.0, 0.0, 0.02)
+        .hermite_key(100.0, 1.0, 0.0, 0.0);
+
+    // p(t) = h10 * dt * m0 + h01 * p1 with dt * m0 = 2
+    for t in [0.25_f32, 0.5, 0.75] {
+        let expected = (t * t * t - 2.0 * t * t + t) * 2.0 + (-2.0 * t * t * t + 3.0 * t * t);
+        assert_near(track.value_at(t * 100.0).unwrap(), expected);
+    }
+}
+
+#[test]
+fn catmull_rom_goes_through_every_key() {
+    let mut track = track();
+    let values = [0.0, 1.0, 0.0, 2.0];
+    for (i, value) in values.iter().enumerate() {
+        track.key(i as f32 * 10.0, *value, Interpolation::CatmullRom);
+    }
+
+    for (i, value) in values.iter().enumerate() {
+        assert_near(track.value_at(i as f32 * 10.0).unwrap(), *value);
+    }
+    // uniform spacing: the middle of the second segment is
+    // (-p0 + 9 p1 + 9 p2 - p3) / 16
+    assert_near(track.value_at(15.0).unwrap(), (9.0 - 2.0) / 16.0);
+}
+
+#[test]
+fn tracks_play_inside_sequences() {
+    let mut position = KeyframeTrack::new(|t: &mut Transform| &mut t.position);
+    position
+        .key(0.0, Vector3::ZERO, Interpolation::Linear)
+        .key(100.0, Vector3::new(10.0, 0.0, 0.0), Interpolation::Linear)
+        .key(200.0, Vector3::new(10.0, 10.0, 0.0), Interpolation::Linear);
+
+    let mut seq = Seq::new();
+    seq.then_delay(50.0).then(position);
+    assert_eq!(seq.duration(), 250.0);
+
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 200.0);
+    assert_eq!(transform.position.x, 10.0);
+    assert_eq!(transform.position.y, 5.0);
+
+    animate.seek(&mut transform, 0.0);
+    assert_eq!(transform.position.x, 0.0);
+}
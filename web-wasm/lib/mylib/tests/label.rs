@@ -0,0 +1,122 @@
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::Transform;
+
+fn x(from: f32, to: f32) -> Tween<Transform> {
+    Tween::new(EasingFunction::Linear, 100.0, Lens::position_x(from, to))
+}
+
+fn y(from: f32, to: f32) -> Tween<Transform> {
+    Tween::new(EasingFunction::Linear, 100.0, Lens::position_y(from, to))
+}
+
+fn starts(seq: &Seq<Transform>) -> Vec<f32> {
+    seq.tweens().iter().map(|tween| tween.start_abs()).collect()
+}
+
+#[test]
+fn positions_read_like_gsap() {
+    assert_eq!(Position::from("500"), Position::At(500.0));
+    assert_eq!(Position::from("+=200"), Position::End(200.0));
+    assert_eq!(Position::from("-=50"), Position::End(-50.0));
+    assert_eq!(Position::from("<"), Position::PreviousStart(0.0));
+    assert_eq!(Position::from("<100"), Position::PreviousStart(100.0));
+    assert_eq!(Position::from(">-=50"), Position::PreviousEnd(-50.0));
+    assert_eq!(
+        Position::from("intro+=200"),
+        Position::Label("intro".into(), 200.0)
+    );
+    assert_eq!(
+        Position::from("intro_end"),
+        Position::Label("intro_end".into(), 0.0)
+    );
+    assert_eq!(Position::from(20.0), Position::At(20.0));
+}
+
+#[test]
+fn children_go_where_they_are_told() {
+    let mut seq = Seq::new();
+    seq.then(x(0.0, 1.0))
+        .insert("<", y(0.0, 1.0))
+        .insert("+=200", x(1.0, 2.0))
+        .insert("-=50", y(1.0, 2.0))
+        .insert(">", x(2.0, 3.0))
+        .insert(10.0, y(5.0, 5.0));
+    assert_eq!(starts(&seq), vec![0.0, 0.0, 300.0, 350.0, 450.0, 10.0]);
+    // the end is where the last child to end does
+    assert_eq!(seq.cycle_duration(), 550.0);
+    seq.then_delay(50.0);
+    assert_eq!(seq.tweens()[6].start_abs(), 550.0);
+
+    // nothing goes before the start, nested sequences keep their layout
+    let mut outer = Seq::new();
+    outer.then_delay(100.0).insert("-=500", seq);
+    assert_eq!(outer.tweens()[1].start_abs(), 0.0);
+}
+
+#[test]
+fn labels_mark_times() {
+    let mut seq = Seq::new();
+    seq.then(x(0.0, 1.0))
+        .add_label("intro_end")
+        .then(y(0.0, 1.0))
+        .insert("intro_end+=50", x(1.0, 2.0))
+        // a new label goes at the end
+        .insert("outro", y(1.0, 0.0))
+        .add_label_at("middle", 75.0);
+    assert_eq!(starts(&seq), vec![0.0, 100.0, 150.0, 250.0]);
+    assert_eq!(seq.label_time("intro_end"), Some(100.0));
+    assert_eq!(seq.label_time("outro"), Some(250.0));
+    assert_eq!(seq.label_time("middle"), Some(75.0));
+    assert_eq!(seq.label_time("nowhere"), None);
+
+    // labels move with the sequence
+    let mut outer = Seq::new();
+    outer.then_delay(1000.0).then(seq);
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(outer.clone()).add_seq(outer);
+    let mut transform = Transform::default();
+    assert_eq!(
+        animate.seek_to_label(&mut transform, "intro_end"),
+        Some(1100.0)
+    );
+    assert_eq!(transform.position.x, 1.0);
+    assert_eq!(animate.seek_to_label(&mut transform, "nowhere"), None);
+}
+
+#[test]
+fn overlapping_children_all_play() {
+    let mut seq = Seq::new();
+    seq.then(x(0.0, 10.0)).insert("<50", y(0.0, 10.0));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 75.0);
+    assert_eq!(transform.position.x, 7.5);
+    assert_eq!(transform.position.y, 2.5);
+
+    // and frame by frame
+    let mut transform = Transform::default();
+    animate.reset(&mut transform);
+    for time in [0.0, 30.0, 75.0] {
+        animate.sequences[0].sample(&mut transform, time);
+    }
+    assert_eq!(transform.position.x, 7.5);
+    assert_eq!(transform.position.y, 2.5);
+
+    // where they write the same field, the one still running wins
+    let mut seq = Seq::new();
+    seq.insert(
+        0.0,
+        Tween::new(EasingFunction::Linear, 200.0, Lens::position_x(0.0, 20.0)),
+    )
+    .insert(0.0, x(-1.0, -1.0));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 50.0);
+    assert_eq!(transform.position.x, -1.0);
+    animate.seek(&mut transform, 150.0);
+    assert_eq!(transform.position.x, 15.0);
+}
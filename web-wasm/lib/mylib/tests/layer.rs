@@ -0,0 +1,162 @@
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+use mylib::layer::*;
+use mylib::lens::*;
+use mylib::{Transform, Vector3};
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Value(f32);
+
+impl Blend for Value {
+    fn blend(&mut self, to: &Self, weight: f32) {
+        self.0.blend(&to.0, weight);
+    }
+
+    fn add(&mut self, rest: &Self, pose: &Self, weight: f32) {
+        self.0.add(&rest.0, &pose.0, weight);
+    }
+
+    fn adopt(&mut self, written: &Self, now: &Self) {
+        self.0.adopt(&written.0, &now.0);
+    }
+}
+
+fn value(v: &mut Value) -> &mut f32 {
+    &mut v.0
+}
+
+fn seq(from: f32, to: f32) -> Seq<Value> {
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::new(value, from, to),
+    ));
+    seq
+}
+
+fn layer(mode: BlendMode, weight: f32, seq: Seq<Value>) -> AnimationLayer<Value> {
+    let mut layer = AnimationLayer::new(mode, weight);
+    layer.add_seq(seq);
+    layer
+}
+
+#[test]
+fn override_layers_blend_by_weight() {
+    let mut animate = AnimateComponent::new();
+    animate
+        .add_seq(seq(0.0, 10.0))
+        .add_layer(layer(BlendMode::Override, 0.5, seq(0.0, -10.0)))
+        .add_layer(layer(BlendMode::Override, 0.0, seq(100.0, 100.0)));
+
+    let mut v = Value(0.0);
+    animate.seek(&mut v, 50.0);
+    assert_eq!(v.0, 0.0);
+    animate.layers[0].weight = 0.25;
+    animate.seek(&mut v, 100.0);
+    assert_eq!(v.0, 5.0);
+}
+
+#[test]
+fn additive_layers_add_their_change_from_rest() {
+    let mut animate = AnimateComponent::new();
+    animate
+        .add_seq(seq(0.0, 10.0))
+        .add_layer(layer(BlendMode::Additive, 0.5, seq(5.0, 9.0)));
+
+    let mut v = Value(5.0);
+    animate.seek(&mut v, 100.0);
+    assert_eq!(v.0, 12.0);
+    // the blend doesn't build on what was written before
+    animate.seek(&mut v, 100.0);
+    assert_eq!(v.0, 12.0);
+
+    animate.reset(&mut v);
+    assert_eq!(v.0, 5.0);
+    animate.seek(&mut v, 50.0);
+    assert_eq!(v.0, 6.0);
+}
+
+#[test]
+fn fields_no_layer_animates_keep_what_else_wrote_them() {
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::position_x(0.0, 10.0),
+    ));
+    let mut walk = AnimationLayer::new(BlendMode::Override, 0.5);
+    walk.add_seq(seq);
+    let mut animate = AnimateComponent::new();
+    animate.add_layer(walk);
+
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 50.0);
+    assert_eq!(transform.position.x, 2.5);
+    // another system scales it between two frames
+    transform.scale = Vector3::new(2.0, 2.0, 2.0);
+    animate.seek(&mut transform, 100.0);
+    assert_eq!(transform.position.x, 5.0);
+    assert_eq!(transform.scale, Vector3::new(2.0, 2.0, 2.0));
+    animate.seek(&mut transform, 20.0);
+    assert_eq!(transform.position.x, 1.0);
+    assert_eq!(transform.scale, Vector3::new(2.0, 2.0, 2.0));
+
+    // a reset still goes back to the snapshot
+    animate.reset(&mut transform);
+    assert_eq!(transform.position.x, 0.0);
+    assert_eq!(transform.scale, Vector3::new(1.0, 1.0, 1.0));
+}
+
+fn frame(world: &mut World, schedule: &mut Schedule, time: f32) -> f32 {
+    world.resource_mut::<AnimationSystemInfo>().currentTime = time;
+    schedule.run(world);
+    world.query::<&Value>().single(world).0
+}
+
+fn completed(world: &mut World) -> Vec<usize> {
+    world
+        .resource_mut::<Events<TweenCompleted>>()
+        .drain()
+        .map(|event| event.seq_index)
+        .collect()
+}
+
+#[test]
+fn crossfades_go_smoothly_from_one_clip_to_the_next() {
+    let mut world = World::new();
+    world.insert_resource(AnimationSystemInfo::default());
+    let mut idle = AnimateComponent::new();
+    idle.add_seq(seq(0.0, 0.0));
+    let entity = world.spawn((Value(0.0), idle)).id();
+
+    let mut schedule = Schedule::new();
+    add_animation_events(&mut world, &mut schedule);
+    schedule.add_system(animation_sys::<Value>);
+
+    assert_eq!(frame(&mut world, &mut schedule, 0.0), 0.0);
+    assert_eq!(frame(&mut world, &mut schedule, 100.0), 0.0);
+    assert_eq!(completed(&mut world), vec![0]);
+
+    let walk = layer(BlendMode::Override, 1.0, seq(10.0, 20.0));
+    world
+        .get_mut::<AnimateComponent<Value>>(entity)
+        .unwrap()
+        .crossfade(walk, 200.0);
+    // the walk starts where the crossfade does
+    assert_eq!(frame(&mut world, &mut schedule, 150.0), 0.0);
+    assert_eq!(frame(&mut world, &mut schedule, 250.0), 10.0);
+    // the walk's sequence is numbered after the idle one
+    assert_eq!(completed(&mut world), vec![1]);
+    assert_eq!(frame(&mut world, &mut schedule, 350.0), 20.0);
+
+    let back = layer(BlendMode::Override, 1.0, seq(0.0, 0.0));
+    let mut animate = world.get_mut::<AnimateComponent<Value>>(entity).unwrap();
+    animate.crossfade(back, 100.0);
+    // the walk covers the idle layer, which is dropped
+    assert!(animate.sequences.is_empty());
+    assert_eq!(animate.layers.len(), 2);
+    assert_eq!(frame(&mut world, &mut schedule, 400.0), 20.0);
+    assert_eq!(frame(&mut world, &mut schedule, 450.0), 10.0);
+    assert_eq!(frame(&mut world, &mut schedule, 500.0), 0.0);
+}
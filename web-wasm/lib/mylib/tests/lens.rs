@@ -0,0 +1,88 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::{Color, Quat, Transform, Vector3};
+
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+}
+
+fn assert_quat_near(a: Quat, b: Quat) {
+    // q and -q are the same rotation
+    assert!(a.dot(b).abs() > 1.0 - 1e-5, "{:?} != {:?}", a, b);
+}
+
+#[test]
+fn lerp_scalars_vectors_and_colors() {
+    assert_near(2.0.lerp(4.0, 0.25), 2.5);
+    // eased ratios can overshoot
+    assert_near(0.0.lerp(10.0, 1.1), 11.0);
+
+    let v = Vector3::new(0.0, 1.0, -2.0).lerp(Vector3::new(2.0, 1.0, 2.0), 0.5);
+    assert_eq!(v.x, 1.0);
+    assert_eq!(v.y, 1.0);
+    assert_eq!(v.z, 0.0);
+
+    let c = Color::rgb(1.0, 0.0, 0.0).lerp(Color::rgba(0.0, 0.0, 1.0, 0.0), 0.5);
+    assert_eq!(c, Color::rgba(0.5, 0.0, 0.5, 0.5));
+}
+
+#[test]
+fn quaternions_slerp_along_the_shortest_arc() {
+    let up = Vector3::new(0.0, 1.0, 0.0);
+    let from = Quat::IDENTITY;
+    let to = Quat::from_axis_angle(up, FRAC_PI_2);
+
+    assert_quat_near(from.lerp(to, 0.0), from);
+    assert_quat_near(from.lerp(to, 1.0), to);
+    assert_quat_near(
+        from.lerp(to, 0.5),
+        Quat::from_axis_angle(up, FRAC_PI_2 / 2.0),
+    );
+
+    // 270° one way is 90° the other
+    let far = Quat::from_axis_angle(up, 3.0 * PI / 2.0);
+    assert_quat_near(
+        from.lerp(far, 0.5),
+        Quat::from_axis_angle(up, -FRAC_PI_2 / 2.0),
+    );
+
+    let mid = from.lerp(to, 0.3);
+    assert_near(mid.dot(mid), 1.0);
+}
+
+#[test]
+fn transform_lenses_write_their_property() {
+    let mut transform = Transform::default();
+
+    let mut tween = Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::position(Vector3::ZERO, Vector3::new(2.0, 4.0, 6.0)),
+    );
+    tween.sample(&mut transform, 50.0);
+    assert_eq!(transform.position.x, 1.0);
+    assert_eq!(transform.position.y, 2.0);
+    assert_eq!(transform.position.z, 3.0);
+
+    Lens::position_y(0.0, 10.0).lerp(&mut transform, 0.1);
+    assert_eq!(transform.position.x, 1.0);
+    assert_eq!(transform.position.y, 1.0);
+
+    Lens::scale(Vector3::ONE, Vector3::splat(3.0)).lerp(&mut transform, 0.5);
+    assert_eq!(transform.scale.z, 2.0);
+
+    let up = Vector3::new(0.0, 1.0, 0.0);
+    let to = Quat::from_axis_angle(up, PI);
+    Lens::rotation(Quat::IDENTITY, to).lerp(&mut transform, 1.0);
+    assert_quat_near(transform.rotation, to);
+}
+
+#[test]
+fn custom_lens_from_an_accessor() {
+    let mut color = Color::rgb(0.0, 0.0, 0.0);
+    let mut lens = Lens::new(|c: &mut Color| &mut c.g, 0.0, 1.0);
+    lens.lerp(&mut color, 0.5);
+    assert_eq!(color, Color::rgb(0.0, 0.5, 0.0));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::{first, linear, second, Pair};
+use mylib::animation::*;
+
+#[test]
+fn a_nested_seq_starts_where_its_parent_puts_it() {
+    let mut inner = Seq::new();
+    inner.then(linear(100.0, second)).then_delay(100.0);
+    let mut outer = Seq::new();
+    outer.then(linear(300.0, first)).then(inner);
+
+    // the children of the inner sequence move along with it
+    let inner = &outer.tweens()[1];
+    assert_eq!(inner.start_abs(), 300.0);
+    assert_eq!(inner.duration(), 200.0);
+    assert_eq!(outer.duration(), 500.0);
+
+    let mut v = Pair(0.0, 0.0);
+    outer.sample(&mut v, 0.0);
+    outer.sample(&mut v, 150.0);
+    assert_eq!(v, Pair(0.5, 0.0));
+    outer.sample(&mut v, 350.0);
+    assert_eq!(v, Pair(1.0, 0.5));
+    outer.sample(&mut v, 450.0);
+    assert_eq!(v, Pair(1.0, 1.0));
+}
+
+#[test]
+fn moving_a_nested_seq_moves_its_children() {
+    let mut inner = Seq::new();
+    inner.then_delay(50.0).then(linear(100.0, second));
+    let mut outer = Seq::new();
+    outer.then(linear(100.0, first)).then(inner.clone());
+    outer.set_start_abs(1000.0);
+    assert_eq!(outer.tweens()[1].start_abs(), 1100.0);
+
+    let mut v = Pair(0.0, 0.0);
+    outer.sample(&mut v, 1000.0);
+    outer.sample(&mut v, 1200.0);
+    assert_eq!(v, Pair(1.0, 0.5));
+}
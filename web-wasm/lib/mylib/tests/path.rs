@@ -0,0 +1,172 @@
+use mylib::animation::*;
+use mylib::curve::*;
+use mylib::{Quat, Transform, Vector3};
+
+fn close(a: Vector3, b: Vector3) -> bool {
+    (a - b).length() < 1e-3
+}
+
+// an L whose legs are 1 and 3 long
+fn elbow() -> Curve {
+    Curve::Polyline(vec![
+        Vector3::ZERO,
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(1.0, 3.0, 0.0),
+    ])
+}
+
+fn position(tween: &mut PathTween<Transform>, time: f32) -> Vector3 {
+    let mut transform = Transform::default();
+    tween.sample(&mut transform, time);
+    transform.position
+}
+
+#[test]
+fn moves_at_constant_speed() {
+    let mut tween = PathTween::position(EasingFunction::Linear, 400.0, elbow());
+    assert_eq!(tween.curve.length(), 4.0);
+    assert!(close(position(&mut tween, 0.0), Vector3::ZERO));
+    assert!(close(
+        position(&mut tween, 100.0),
+        Vector3::new(1.0, 0.0, 0.0)
+    ));
+    assert!(close(
+        position(&mut tween, 300.0),
+        Vector3::new(1.0, 2.0, 0.0)
+    ));
+    assert!(close(
+        position(&mut tween, 400.0),
+        Vector3::new(1.0, 3.0, 0.0)
+    ));
+
+    // by parameter each leg takes half the time
+    tween.arc_length = false;
+    assert!(close(
+        position(&mut tween, 200.0),
+        Vector3::new(1.0, 0.0, 0.0)
+    ));
+
+    // a bezier with its controls bunched at one end
+    let curve = ArcLengthCurve::new(Curve::Cubic {
+        start: Vector3::ZERO,
+        segments: vec![[
+            Vector3::new(0.1, 0.0, 0.0),
+            Vector3::new(0.2, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 0.0),
+        ]],
+    });
+    assert!((curve.length() - 10.0).abs() < 1e-3);
+    for i in 0..=10 {
+        let ratio = i as f32 / 10.0;
+        assert!((curve.at(ratio, true).0.x - ratio * 10.0).abs() < 0.05);
+    }
+}
+
+#[test]
+fn splines_go_through_their_ends() {
+    let points = vec![
+        Vector3::ZERO,
+        Vector3::new(1.0, 1.0, 0.0),
+        Vector3::new(2.0, 0.0, 1.0),
+        Vector3::new(3.0, 2.0, 0.0),
+    ];
+    let quadratic = Curve::Quadratic {
+        start: points[0],
+        segments: vec![[points[1], points[2]], [points[3], points[0]]],
+    };
+    let cubic = Curve::Cubic {
+        start: points[0],
+        segments: vec![[points[1], points[2], points[3]]],
+    };
+    for (curve, end) in [
+        (quadratic, points[0]),
+        (cubic, points[3]),
+        (Curve::CatmullRom(points.clone()), points[3]),
+    ] {
+        let curve = ArcLengthCurve::new(curve);
+        assert!(close(curve.at(0.0, true).0, points[0]));
+        assert!(close(curve.at(1.0, true).0, end));
+    }
+
+    // catmull-rom goes through every point
+    let curve = Curve::CatmullRom(points.clone());
+    for (i, point) in points.iter().enumerate() {
+        assert!(close(curve.at(i as f32).0, *point));
+    }
+    // and is smooth where segments meet
+    let (_, before) = curve.at(1.0 - 1e-4);
+    let (_, after) = curve.at(1.0);
+    assert!(close(before, after));
+}
+
+#[test]
+fn eases_and_overshoots_along_the_tangents() {
+    let line = Curve::Polyline(vec![Vector3::ZERO, Vector3::new(10.0, 0.0, 0.0)]);
+    let mut tween = PathTween::position(EasingFunction::QuadraticIn, 100.0, line.clone());
+    assert!(close(
+        position(&mut tween, 50.0),
+        Vector3::new(2.5, 0.0, 0.0)
+    ));
+
+    let mut tween = PathTween::position(EasingFunction::BackIn, 100.0, line);
+    let x = position(&mut tween, 30.0).x;
+    assert!(x < 0.0, "{}", x);
+}
+
+#[test]
+fn turns_to_face_the_tangent() {
+    let forward = Vector3::new(0.0, 0.0, 1.0);
+    let mut tween = PathTween::position(EasingFunction::Linear, 400.0, elbow()).facing(forward);
+    let mut transform = Transform::default();
+
+    let rotate = |q: Quat, v: Vector3| {
+        let r =
+            q * Quat {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+                w: 0.0,
+            } * q.conjugate();
+        Vector3::new(r.x, r.y, r.z)
+    };
+    tween.sample(&mut transform, 50.0);
+    assert!(close(
+        rotate(transform.rotation, forward),
+        Vector3::new(1.0, 0.0, 0.0)
+    ));
+    tween.sample(&mut transform, 250.0);
+    assert!(close(
+        rotate(transform.rotation, forward),
+        Vector3::new(0.0, 1.0, 0.0)
+    ));
+
+    // straight back along the forward axis
+    let back = Quat::from_rotation_arc(forward, forward * -1.0);
+    assert!(close(rotate(back, forward), forward * -1.0));
+}
+
+#[test]
+fn plays_in_a_sequence() {
+    let mut seq = Seq::new();
+    seq.then_delay(100.0)
+        .then(PathTween::position(EasingFunction::Linear, 400.0, elbow()))
+        .then(PathTween::position(
+            EasingFunction::Linear,
+            100.0,
+            Curve::Polyline(vec![
+                Vector3::new(1.0, 3.0, 0.0),
+                Vector3::new(1.0, 3.0, 5.0),
+            ]),
+        ));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    assert_eq!(animate.total_time(), 600.0);
+
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 300.0);
+    assert!(close(transform.position, Vector3::new(1.0, 1.0, 0.0)));
+    animate.seek(&mut transform, 550.0);
+    assert!(close(transform.position, Vector3::new(1.0, 3.0, 2.5)));
+    animate.seek(&mut transform, 0.0);
+    assert!(close(transform.position, Vector3::ZERO));
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use bevy_ecs::prelude::*;
+use common::{value, Harness, Value};
+use mylib::animation::*;
+use mylib::lens::*;
+
+fn spawn(harness: &mut Harness<Value>, speed: f32) -> Entity {
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(Tween::new(
+        EasingFunction::Linear,
+        1000.0,
+        Lens::new(value, 0.0, 1000.0),
+    ));
+    let mut player = AnimationPlayer::new();
+    player.speed = speed;
+    harness.world.spawn((Value(0.0), animate, player)).id()
+}
+
+#[test]
+fn players_keep_their_own_time() {
+    let mut harness = Harness::new(100.0);
+    let fast = spawn(&mut harness, 2.0);
+    let slow = spawn(&mut harness, 0.5);
+
+    harness.step(4);
+    assert_eq!(
+        harness.world.get::<AnimationPlayer>(fast).unwrap().time,
+        600.0
+    );
+    assert_eq!(harness.target(fast).0, 600.0);
+    assert_eq!(harness.target(slow).0, 150.0);
+
+    // pausing or seeking one leaves the other alone
+    harness
+        .world
+        .get_mut::<AnimationPlayer>(fast)
+        .unwrap()
+        .pause();
+    harness
+        .world
+        .get_mut::<AnimationPlayer>(slow)
+        .unwrap()
+        .seek(500.0);
+    harness.step(1);
+    assert_eq!(harness.target(fast).0, 600.0);
+    assert_eq!(harness.target(slow).0, 550.0);
+
+    // each stops at its own end
+    harness
+        .world
+        .get_mut::<AnimationPlayer>(fast)
+        .unwrap()
+        .play();
+    harness.step(10);
+    assert_eq!(harness.target(fast).0, 1000.0);
+    assert_eq!(harness.target(slow).0, 1000.0);
+    assert_eq!(
+        harness.world.get::<AnimationPlayer>(slow).unwrap().time,
+        1000.0
+    );
+}
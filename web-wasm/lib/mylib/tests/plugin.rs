@@ -0,0 +1,159 @@
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+use mylib::lens::*;
+use mylib::plugin::*;
+
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+struct Value(f32);
+
+fn value(v: &mut Value) -> &mut f32 {
+    &mut v.0
+}
+
+fn tween(duration: f32) -> Tween<Value> {
+    Tween::new(EasingFunction::Linear, duration, Lens::new(value, 0.0, 1.0))
+}
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Other(f32);
+
+fn other(v: &mut Other) -> &mut f32 {
+    &mut v.0
+}
+
+#[derive(Resource, Default)]
+struct Log(Vec<&'static str>);
+
+fn probe(name: &'static str) -> impl FnMut(ResMut<Log>) {
+    move |mut log: ResMut<Log>| log.0.push(name)
+}
+
+fn world() -> (World, Schedule) {
+    let mut world = World::new();
+    let mut schedule = Schedule::new();
+    AnimationPlugin::<Value>::new().build(&mut world, &mut schedule);
+    AnimationResourcePlugin::<Value>::new().build(&mut world, &mut schedule);
+    // the clock stays where the tests put it
+    world.resource_mut::<AnimationSystemInfo>().timeScale = 0.0;
+    (world, schedule)
+}
+
+#[test]
+fn sets_run_in_order_around_user_systems() {
+    let (mut world, mut schedule) = world();
+    world.init_resource::<Log>();
+    schedule.add_system(probe("after").after(AnimationSet::Sync));
+    schedule.add_system(probe("sync").in_set(AnimationSet::Sync));
+    schedule.add_system(probe("evaluate").in_set(AnimationSet::Evaluate));
+    schedule.add_system(probe("controller").in_set(AnimationSet::Controller));
+    schedule.add_system(probe("clock").in_set(AnimationSet::Clock));
+    schedule.add_system(probe("before").before(AnimationSet::Clock));
+    schedule.run(&mut world);
+    assert_eq!(
+        world.resource::<Log>().0,
+        ["before", "clock", "controller", "evaluate", "sync", "after"]
+    );
+}
+
+#[test]
+fn totals_are_measured_before_anything_plays() {
+    let (mut world, mut schedule) = world();
+    let mut animate = AnimateResource::new();
+    animate.add_tween(tween(300.0));
+    world.insert_resource(animate);
+    world.insert_resource(Value(5.0));
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(tween(100.0).with_tag(1));
+    let entity = world.spawn((Value(5.0), animate)).id();
+
+    // added by a system that runs in the same frame
+    let spawn = |mut commands: Commands| {
+        let mut animate = AnimateComponent::new();
+        animate.add_tween(tween(400.0));
+        commands.spawn((Value(5.0), animate));
+    };
+    schedule.add_systems(
+        (spawn, apply_system_buffers)
+            .chain()
+            .before(AnimationSet::Clock),
+    );
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 400.0);
+    assert_eq!(world.resource::<Value>().0, 0.0);
+    assert_eq!(world.get::<Value>(entity).unwrap().0, 0.0);
+}
+
+#[test]
+fn the_clock_waits_for_the_longest_of_several_types() {
+    let (mut world, mut schedule) = world();
+    AnimationPlugin::<Other>::new().build(&mut world, &mut schedule);
+    let mut info = world.resource_mut::<AnimationSystemInfo>();
+    info.timeScale = 1.0;
+    info.fixedDt = Some(100.0);
+
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(tween(1000.0));
+    let long = world.spawn((Value(0.0), animate)).id();
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(Tween::new(
+        EasingFunction::Linear,
+        200.0,
+        Lens::new(other, 0.0, 1.0),
+    ));
+    let short = world.spawn((Other(0.0), animate)).id();
+
+    for _ in 0..15 {
+        schedule.run(&mut world);
+    }
+    let info = world.resource::<AnimationSystemInfo>();
+    assert_eq!(info.totalTime, 1000.0);
+    assert!(!info.needsUpdate);
+    assert_eq!(world.get::<Value>(long).unwrap().0, 1.0);
+
+    // `needsUpdate` reaches every controller, whichever runs first
+    world
+        .get_mut::<AnimateComponent<Other>>(short)
+        .unwrap()
+        .bypass_change_detection()
+        .add_tween(Tween::new(
+            EasingFunction::Linear,
+            1500.0,
+            Lens::new(other, 0.0, 1.0),
+        ));
+    world.resource_mut::<AnimationSystemInfo>().needsUpdate = true;
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 1500.0);
+    world
+        .get_mut::<AnimateComponent<Value>>(long)
+        .unwrap()
+        .bypass_change_detection()
+        .add_tween(tween(2000.0));
+    world.resource_mut::<AnimationSystemInfo>().needsUpdate = true;
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 2000.0);
+}
+
+#[test]
+fn events_of_a_frame_can_be_read_after_it() {
+    let (mut world, mut schedule) = world();
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(tween(100.0).with_tag(7));
+    world.spawn((Value(0.0), animate));
+    world.init_resource::<Log>();
+    schedule.add_system(
+        (|mut log: ResMut<Log>, mut events: EventReader<TweenCompleted>| {
+            for event in events.iter() {
+                assert_eq!(event.user_tag, 7);
+                log.0.push("completed");
+            }
+        })
+        .after(AnimationSet::Sync),
+    );
+
+    schedule.run(&mut world);
+    world.resource_mut::<AnimationSystemInfo>().currentTime = 100.0;
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Log>().0, ["completed"]);
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Log>().0, ["completed"]);
+}
@@ -0,0 +1,152 @@
+use bevy_ecs::prelude::Component;
+use bevy_reflect::Reflect;
+use mylib::animation::*;
+use mylib::clip::*;
+use mylib::property::*;
+use mylib::{Transform, Vector3};
+
+#[derive(Component, Reflect, Default, Debug)]
+struct Light {
+    intensity: f32,
+    offset: Vector3,
+    name: String,
+}
+
+#[test]
+fn paths_reach_any_reflected_field() {
+    let mut light = Light::default();
+
+    let mut tween = Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        PropertyLens::<Light, f32>::new("Light.intensity", 0.0, 10.0).unwrap(),
+    );
+    tween.sample(&mut light, 50.0);
+    assert_eq!(light.intensity, 5.0);
+
+    let mut offset = PropertyLens::<Light, f32>::new("Light.offset.z", 1.0, 3.0).unwrap();
+    offset.lerp(&mut light, 0.5);
+    assert_eq!(light.offset.z, 2.0);
+
+    let mut transform = Transform::default();
+    let mut scale = PropertyLens::<Transform, Vector3>::new(
+        "Transform.scale",
+        Vector3::ONE,
+        Vector3::splat(3.0),
+    )
+    .unwrap();
+    scale.lerp(&mut transform, 0.5);
+    assert_eq!(transform.scale, Vector3::splat(2.0));
+}
+
+#[test]
+fn bad_paths_are_rejected_up_front() {
+    let err = PropertyLens::<Light, f32>::new("Light", 0.0, 1.0).unwrap_err();
+    assert_eq!(
+        err,
+        PathError::Malformed {
+            path: "Light".into()
+        }
+    );
+
+    let err = PropertyLens::<Light, f32>::new("Transform.position.y", 0.0, 1.0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Transform.position.y` does not start with the animated component `Light`"
+    );
+
+    let err = PropertyLens::<Light, f32>::new("Light.brightness", 0.0, 1.0).unwrap_err();
+    assert!(matches!(err, PathError::Unresolved { .. }), "{:?}", err);
+    assert!(err.to_string().contains("brightness"), "{}", err);
+
+    let err = PropertyLens::<Light, f32>::new("Light.name", 0.0, 1.0).unwrap_err();
+    assert_eq!(err.to_string(), "`Light.name` is a `String`, not a `f32`");
+}
+
+#[test]
+fn clips_target_paths() {
+    let ron = r#"(
+    name: "path",
+    sequences: [
+        (
+            steps: [
+                Tween((
+                    duration: 100.0,
+                    ease: Linear,
+                    target: Path(
+                        path: "Transform.position.y",
+                        from: 0.0,
+                        to: 4.0,
+                    ),
+                )),
+                Tween((
+                    duration: 100.0,
+                    ease: Linear,
+                    target: Path(
+                        path: "Transform.rotation",
+                        from: (x: 0.0, y: 0.0, z: 0.0, w: 1.0),
+                        to: (x: 0.0, y: 1.0, z: 0.0, w: 0.0),
+                    ),
+                )),
+            ],
+        ),
+    ],
+)"#;
+    let clip = Clip::from_ron(ron).unwrap();
+    match &clip.sequences[0].steps[1] {
+        StepDef::Tween(TweenDef {
+            target: TransformProperty::Path { from, .. },
+            ..
+        }) => assert!(matches!(from, PathValue::Quat(_)), "{:?}", from),
+        step => panic!("{:?}", step),
+    }
+    assert_eq!(Clip::from_json(&clip.to_json().unwrap()).unwrap(), clip);
+
+    let mut animate = clip.build().unwrap();
+    let mut transform = Transform::default();
+    animate.seek(&mut transform, 50.0);
+    assert_eq!(transform.position.y, 2.0);
+    animate.seek(&mut transform, 200.0);
+    assert_eq!(transform.rotation.y, 1.0);
+}
+
+#[test]
+fn clips_with_bad_paths_fail_to_load() {
+    let clip = |target: &str| {
+        format!(
+            r#"{{ "name": "bad", "sequences": [{{ "steps": [
+                {{ "Tween": {{ "duration": 1.0, "ease": "Linear", "target": {} }} }}
+            ] }}] }}"#,
+            target
+        )
+    };
+
+    let err = Clip::from_json(&clip(
+        r#"{ "Path": { "path": "Transform.positon.y", "from": 0.0, "to": 1.0 } }"#,
+    ))
+    .unwrap_err();
+    assert!(
+        matches!(err, ClipError::Path(PathError::Unresolved { .. })),
+        "{:?}",
+        err
+    );
+
+    let err = Clip::from_json(&clip(
+        r#"{ "Path": { "path": "Transform.position", "from": 0.0, "to": 1.0 } }"#,
+    ))
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid clip target: `Transform.position` is a `Vector3`, not a `f32`"
+    );
+
+    let err = Clip::from_json(&clip(
+        r#"{ "Path": { "path": "Transform.position.x", "from": 0.0, "to": { "x": 1.0, "y": 1.0, "z": 1.0 } } }"#,
+    ))
+    .unwrap_err();
+    assert!(
+        matches!(err, ClipError::MismatchedValues { .. }),
+        "{:?}",
+        err
+    );
+}
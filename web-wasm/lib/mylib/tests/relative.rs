@@ -0,0 +1,206 @@
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+use mylib::clip::*;
+use mylib::lens::*;
+use mylib::Transform;
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Value(f32);
+
+fn value(v: &mut Value) -> &mut f32 {
+    &mut v.0
+}
+
+fn tween(lens: RelativeLens<Value, f32>) -> Tween<Value> {
+    Tween::new(EasingFunction::Linear, 100.0, lens)
+}
+
+fn frame(world: &mut World, schedule: &mut Schedule, time: f32) {
+    world.resource_mut::<AnimationSystemInfo>().currentTime = time;
+    schedule.run(world);
+}
+
+fn values(world: &mut World) -> Vec<f32> {
+    let mut values: Vec<f32> = world.query::<&Value>().iter(world).map(|v| v.0).collect();
+    values.sort_by(f32::total_cmp);
+    values
+}
+
+#[test]
+fn to_starts_where_the_target_is_when_the_tween_starts() {
+    let mut seq = Seq::new();
+    seq.then_delay(100.0)
+        .then(tween(RelativeLens::to(value, 10.0)));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+
+    let mut v = Value(0.0);
+    animate.seek(&mut v, 50.0);
+    // nothing captured yet, the value is left alone
+    assert_eq!(v.0, 0.0);
+    v.0 = 4.0;
+    animate.seek(&mut v, 150.0);
+    assert_eq!(v.0, 7.0);
+    // the start stays captured when seeking back
+    animate.seek(&mut v, 50.0);
+    assert_eq!(v.0, 4.0);
+    animate.seek(&mut v, 200.0);
+    assert_eq!(v.0, 10.0);
+}
+
+#[test]
+fn by_moves_every_entity_from_where_it_is() {
+    let mut world = World::new();
+    world.insert_resource(AnimationSystemInfo::default());
+    let mut nudge = AnimateComponent::new();
+    nudge.add_tween(tween(RelativeLens::by(value, 1.0)));
+    for start in [0.0, 10.0, -3.0] {
+        world.spawn((Value(start), nudge.clone()));
+    }
+
+    let mut schedule = Schedule::new();
+    add_animation_events(&mut world, &mut schedule);
+    schedule.add_system(animation_sys::<Value>);
+
+    frame(&mut world, &mut schedule, 0.0);
+    frame(&mut world, &mut schedule, 50.0);
+    assert_eq!(values(&mut world), vec![-2.5, 0.5, 10.5]);
+    frame(&mut world, &mut schedule, 150.0);
+    assert_eq!(values(&mut world), vec![-2.0, 1.0, 11.0]);
+
+    // a reset forgets the captured starts
+    world.resource_mut::<AnimationSystemInfo>().reset();
+    schedule.run(&mut world);
+    assert_eq!(values(&mut world), vec![-3.0, 0.0, 10.0]);
+    world
+        .query::<&mut Value>()
+        .for_each_mut(&mut world, |mut v| v.0 += 100.0);
+    world.resource_mut::<AnimationSystemInfo>().state = AnimationSystemState::Play;
+    frame(&mut world, &mut schedule, 0.0);
+    frame(&mut world, &mut schedule, 100.0);
+    assert_eq!(values(&mut world), vec![98.0, 101.0, 111.0]);
+}
+
+fn layered(additive_first: bool) -> AnimateComponent<Value> {
+    let mut absolute = Seq::new();
+    absolute.then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::new(value, 0.0, 10.0),
+    ));
+    let mut additive = Seq::new();
+    additive.then(tween(RelativeLens::additive(value, 1.0)));
+
+    let mut animate = AnimateComponent::new();
+    if additive_first {
+        animate.add_seq(additive).add_seq(absolute);
+    } else {
+        animate.add_seq(absolute).add_seq(additive);
+    }
+    animate
+}
+
+#[test]
+fn additive_layers_on_top_in_any_order() {
+    for additive_first in [false, true] {
+        let mut world = World::new();
+        world.insert_resource(AnimationSystemInfo::default());
+        world.spawn((Value(0.0), layered(additive_first)));
+        let mut schedule = Schedule::new();
+        add_animation_events(&mut world, &mut schedule);
+        schedule.add_system(animation_sys::<Value>);
+
+        frame(&mut world, &mut schedule, 0.0);
+        frame(&mut world, &mut schedule, 50.0);
+        assert_eq!(values(&mut world), vec![5.5]);
+        // once everything ended the offset stays, it isn't added again
+        for time in [100.0, 120.0, 140.0] {
+            frame(&mut world, &mut schedule, time);
+            assert_eq!(values(&mut world), vec![11.0]);
+        }
+        world.resource_mut::<AnimationSystemInfo>().seek(25.0);
+        schedule.run(&mut world);
+        assert_eq!(values(&mut world), vec![2.75]);
+    }
+}
+
+#[test]
+fn additive_tweens_add_up() {
+    let mut animate = AnimateComponent::new();
+    animate
+        .add_tween(tween(RelativeLens::additive(value, 1.0)))
+        .add_tween(tween(RelativeLens::additive(value, -3.0)));
+
+    let mut v = Value(2.0);
+    for time in [0.0, 100.0, 50.0, 100.0] {
+        animate.seek(&mut v, time);
+    }
+    assert_eq!(v.0, 0.0);
+    animate.seek(&mut v, 50.0);
+    assert_eq!(v.0, 1.0);
+    animate.seek(&mut v, 0.0);
+    assert_eq!(v.0, 2.0);
+}
+
+#[test]
+fn additive_rotations_compose() {
+    use mylib::Quat;
+    use mylib::Vector3;
+
+    let quarter = |axis: Vector3| Quat::from_axis_angle(axis, std::f32::consts::FRAC_PI_2);
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        RelativeLens::rotation(Relative::Additive, quarter(Vector3::new(0.0, 0.0, 1.0))),
+    ));
+
+    let start = quarter(Vector3::new(1.0, 0.0, 0.0));
+    let mut transform = Transform {
+        rotation: start,
+        ..Transform::default()
+    };
+    animate.seek(&mut transform, 100.0);
+    let expected = start * quarter(Vector3::new(0.0, 0.0, 1.0));
+    assert!(transform.rotation.dot(expected).abs() > 0.9999);
+    animate.seek(&mut transform, 0.0);
+    assert!(transform.rotation.dot(start).abs() > 0.9999);
+}
+
+#[test]
+fn clips_can_be_relative() {
+    let clip = Clip::from_ron(
+        r#"(
+            name: "nudge right",
+            sequences: [(
+                steps: [Tween((duration: 100, ease: Linear, target: PositionX(to: 1), relative: By))],
+            )],
+        )"#,
+    )
+    .unwrap();
+    assert_eq!(Clip::from_ron(&clip.to_ron().unwrap()).unwrap(), clip);
+
+    for x in [0.0, 4.0] {
+        let mut transform = Transform::default();
+        transform.position.x = x;
+        let mut animate = clip.build().unwrap();
+        animate.seek(&mut transform, 100.0);
+        assert_eq!(transform.position.x, x + 1.0);
+    }
+}
+
+#[test]
+fn bad_relative_clips_are_errors() {
+    let absolute = r#"{ "name": "x", "sequences": [{ "steps": [{ "Tween": {
+        "duration": 1.0, "ease": "Linear", "target": { "PositionX": { "to": 1.0 } }
+    } }] }] }"#;
+    let err = Clip::from_json(absolute).unwrap_err();
+    assert!(err.to_string().contains("needs a `from`"), "{}", err);
+
+    let path = r#"{ "name": "x", "sequences": [{ "steps": [{ "Tween": {
+        "duration": 1.0, "ease": "Linear", "relative": "To",
+        "target": { "Path": { "path": "Transform.position.x", "from": 0.0, "to": 1.0 } }
+    } }] }] }"#;
+    let err = Clip::from_json(path).unwrap_err();
+    assert!(err.to_string().contains("relatively"), "{}", err);
+}
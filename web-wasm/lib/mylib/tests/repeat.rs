@@ -0,0 +1,74 @@
+mod common;
+
+use common::{value, Value};
+use mylib::animation::*;
+use mylib::lens::*;
+
+// 0 to 1 over 100ms, then 1 to 2 over 100ms
+fn seq() -> Seq<Value> {
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::new(value, 0.0, 1.0),
+    ))
+    .then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::new(value, 1.0, 2.0),
+    ));
+    seq
+}
+
+fn at(seq: &mut Seq<Value>, time: f32) -> f32 {
+    let mut v = Value(0.0);
+    seq.seek(&mut v, time);
+    v.0
+}
+
+#[test]
+fn repeats_the_whole_sequence() {
+    let mut seq = seq();
+    seq.repeat(Repeat::Count(2));
+    assert_eq!(seq.duration(), 600.0);
+    assert_eq!(at(&mut seq, 50.0), 0.5);
+    assert_eq!(at(&mut seq, 250.0), 0.5);
+    assert_eq!(at(&mut seq, 550.0), 1.5);
+    // holds the end after the last cycle
+    assert_eq!(at(&mut seq, 900.0), 2.0);
+
+    seq.repeat(Repeat::Infinite);
+    assert_eq!(seq.duration(), f32::INFINITY);
+    assert_eq!(at(&mut seq, 200.0 * 1000.0 + 150.0), 1.5);
+}
+
+#[test]
+fn yoyo_plays_odd_cycles_backwards() {
+    let mut seq = seq();
+    seq.repeat(Repeat::Count(3)).yoyo(true);
+    // forwards, backwards, forwards, backwards
+    assert_eq!(at(&mut seq, 50.0), 0.5);
+    assert_eq!(at(&mut seq, 250.0), 1.5);
+    assert_eq!(at(&mut seq, 350.0), 0.5);
+    assert_eq!(at(&mut seq, 450.0), 0.5);
+    assert_eq!(at(&mut seq, 750.0), 0.5);
+    // a backwards cycle ends at the start
+    assert_eq!(at(&mut seq, 800.0), 0.0);
+}
+
+#[test]
+fn repeat_delay_holds_between_cycles() {
+    let mut seq = seq();
+    seq.repeat(Repeat::Count(1)).repeat_delay(50.0);
+    assert_eq!(seq.duration(), 450.0);
+    assert_eq!(at(&mut seq, 200.0), 2.0);
+    assert_eq!(at(&mut seq, 225.0), 2.0);
+    assert_eq!(at(&mut seq, 250.0), 0.0);
+    assert_eq!(at(&mut seq, 300.0), 0.5);
+
+    // a yoyo waits at the far end before coming back
+    seq.yoyo(true);
+    assert_eq!(at(&mut seq, 225.0), 2.0);
+    assert_eq!(at(&mut seq, 300.0), 1.5);
+    assert_eq!(at(&mut seq, 450.0), 0.0);
+}
@@ -0,0 +1,85 @@
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Value(f32);
+
+#[derive(Clone, Debug)]
+struct ValueLens {
+    start: f32,
+    end: f32,
+}
+
+impl TweenTarget<Value> for ValueLens {
+    fn lerp(&mut self, target: &mut Value, ratio: f32) {
+        target.0 = self.start + (self.end - self.start) * ratio;
+    }
+}
+
+fn animate(start: f32, end: f32) -> AnimateComponent<Value> {
+    let mut animate = AnimateComponent::new();
+    animate.add_tween(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        ValueLens { start, end },
+    ));
+    animate
+}
+
+fn frame(world: &mut World, schedule: &mut Schedule, time: f32) {
+    world.resource_mut::<AnimationSystemInfo>().currentTime = time;
+    schedule.run(world);
+}
+
+fn values(world: &mut World) -> Vec<f32> {
+    let mut values: Vec<f32> = world.query::<&Value>().iter(world).map(|v| v.0).collect();
+    values.sort_by(f32::total_cmp);
+    values
+}
+
+#[test]
+fn reset_restores_every_entity_to_its_snapshot() {
+    let mut world = World::new();
+    world.insert_resource(AnimationSystemInfo::default());
+    // the tweens don't start from the original values, lerping them back to
+    // 0 would not be a reset
+    world.spawn((Value(1.0), animate(5.0, 10.0)));
+    world.spawn((Value(2.0), animate(-5.0, -10.0)));
+
+    let mut schedule = Schedule::new();
+    add_animation_events(&mut world, &mut schedule);
+    schedule.add_system(animation_sys::<Value>);
+
+    frame(&mut world, &mut schedule, 0.0);
+    frame(&mut world, &mut schedule, 50.0);
+    assert_eq!(values(&mut world), vec![-7.5, 7.5]);
+
+    world.resource_mut::<AnimationSystemInfo>().reset();
+    schedule.run(&mut world);
+    assert_eq!(values(&mut world), vec![1.0, 2.0]);
+
+    // holds there until played again, then starts over
+    schedule.run(&mut world);
+    assert_eq!(values(&mut world), vec![1.0, 2.0]);
+
+    world.resource_mut::<AnimationSystemInfo>().state = AnimationSystemState::Play;
+    frame(&mut world, &mut schedule, 20.0);
+    assert_eq!(values(&mut world), vec![-6.0, 6.0]);
+}
+
+#[test]
+fn reset_after_capture_returns_to_the_new_snapshot() {
+    let mut animate = animate(0.0, 10.0);
+    let mut value = Value(3.0);
+
+    animate.capture(&value);
+    animate.seek(&mut value, 100.0);
+    assert_eq!(value, Value(10.0));
+
+    value.0 = 4.0;
+    animate.capture(&value);
+    animate.seek(&mut value, 50.0);
+    animate.reset(&mut value);
+    assert_eq!(value, Value(4.0));
+    assert_eq!(animate.snapshot(), Some(&Value(4.0)));
+}
@@ -0,0 +1,119 @@
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+use mylib::lens::*;
+
+#[derive(Resource, Component, Clone, Debug, PartialEq)]
+struct Shake(f32);
+
+fn shake(s: &mut Shake) -> &mut f32 {
+    &mut s.0
+}
+
+fn tween(duration: f32, from: f32, to: f32) -> Tween<Shake> {
+    Tween::new(EasingFunction::Linear, duration, Lens::new(shake, from, to))
+}
+
+fn frame(world: &mut World, schedule: &mut Schedule, time: f32) -> f32 {
+    world.resource_mut::<AnimationSystemInfo>().currentTime = time;
+    schedule.run(world);
+    world.resource::<Shake>().0
+}
+
+fn world() -> (World, Schedule) {
+    let mut world = World::new();
+    world.insert_resource(AnimationSystemInfo::default());
+    world.insert_resource(Shake(0.0));
+    let mut animate = AnimateResource::new();
+    animate.add_tween(tween(200.0, 0.0, 1.0).with_tag(3));
+    world.insert_resource(animate);
+
+    let mut schedule = Schedule::new();
+    add_animation_events(&mut world, &mut schedule);
+    schedule.add_system(animation_resource_controller_sys::<Shake>);
+    schedule.add_system(
+        animation_resource_sys::<Shake>.after(animation_resource_controller_sys::<Shake>),
+    );
+    (world, schedule)
+}
+
+#[test]
+fn resources_play_on_the_global_clock() {
+    let (mut world, mut schedule) = world();
+    assert_eq!(frame(&mut world, &mut schedule, 0.0), 0.0);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 200.0);
+    assert_eq!(frame(&mut world, &mut schedule, 50.0), 0.25);
+
+    world.resource_mut::<AnimationSystemInfo>().seek(150.0);
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Shake>().0, 0.75);
+
+    assert_eq!(frame(&mut world, &mut schedule, 200.0), 1.0);
+    let events: Vec<TweenCompleted> = world
+        .resource_mut::<Events<TweenCompleted>>()
+        .drain()
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, Entity::PLACEHOLDER);
+    assert_eq!(events[0].user_tag, 3);
+
+    world.resource_mut::<AnimationSystemInfo>().reset();
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Shake>().0, 0.0);
+}
+
+#[test]
+fn nothing_happens_without_the_resource() {
+    let (mut world, mut schedule) = world();
+    world.remove_resource::<Shake>();
+    world.resource_mut::<AnimationSystemInfo>().currentTime = 100.0;
+    schedule.run(&mut world);
+    world.insert_resource(Shake(5.0));
+    // the snapshot is taken on the first frame the resource is there
+    assert_eq!(frame(&mut world, &mut schedule, 100.0), 0.5);
+    world.resource_mut::<AnimationSystemInfo>().reset();
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<Shake>().0, 5.0);
+}
+
+#[test]
+fn the_clock_waits_for_resources_and_components() {
+    let (mut world, mut schedule) = world();
+    let mut short = AnimateComponent::new();
+    short.add_tween(tween(100.0, 0.0, 1.0));
+    world.spawn((Shake(0.0), short));
+    schedule.add_system(animation_controller_sys::<Shake>);
+    schedule.add_system(animation_sys::<Shake>);
+
+    for _ in 0..3 {
+        world.resource_mut::<AnimationSystemInfo>().needsUpdate = true;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 200.0);
+    }
+
+    // a shorter animation or none leaves the components' total
+    let mut animate = AnimateResource::new();
+    animate.add_tween(tween(50.0, 0.0, 1.0));
+    world.insert_resource(animate);
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 100.0);
+    world.remove_resource::<AnimateResource<Shake>>();
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 100.0);
+}
+
+#[test]
+fn the_clock_shrinks_with_the_resource_animation() {
+    let (mut world, mut schedule) = world();
+    assert_eq!(frame(&mut world, &mut schedule, 0.0), 0.0);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 200.0);
+
+    let mut animate = AnimateResource::new();
+    animate.add_tween(tween(80.0, 0.0, 1.0));
+    world.insert_resource(animate);
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 80.0);
+
+    world.remove_resource::<AnimateResource<Shake>>();
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<AnimationSystemInfo>().totalTime, 0.0);
+}
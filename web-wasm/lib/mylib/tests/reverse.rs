@@ -0,0 +1,67 @@
+mod common;
+
+use bevy_ecs::prelude::*;
+use common::{value, Harness, Value};
+use mylib::animation::*;
+use mylib::lens::*;
+
+// two tweens back to back on the same field, 0 to 1 then 5 to 10
+fn animate() -> AnimateComponent<Value> {
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::new(value, 0.0, 1.0),
+    ))
+    .then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        Lens::new(value, 5.0, 10.0),
+    ));
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+    animate
+}
+
+// runs `frames` frames and returns what each of them left
+fn play(harness: &mut Harness<Value>, entity: Entity, frames: usize) -> Vec<f32> {
+    (0..frames)
+        .map(|_| harness.step(1).target(entity).0)
+        .collect()
+}
+
+#[test]
+fn the_earlier_tween_wins_on_the_way_back() {
+    let mut harness = Harness::new(25.0);
+    let entity = harness.spawn("value", Value(0.0), animate());
+    play(&mut harness, entity, 1);
+    harness.info().seek(200.0);
+    harness.info().timeScale = -2.0;
+    assert_eq!(
+        play(&mut harness, entity, 6),
+        // the second tween goes back to its start once the clock is on the
+        // first one, then the first one plays back to 0
+        [7.5, 5.0, 0.5, 0.0, 0.0, 0.0]
+    );
+    assert_eq!(harness.info().currentTime, 0.0);
+}
+
+#[test]
+fn players_stop_at_0_when_played_backwards() {
+    let mut harness = Harness::new(25.0);
+    let mut player = AnimationPlayer::new();
+    player.speed = -2.0;
+    let entity = harness.world.spawn((Value(0.0), animate(), player)).id();
+    play(&mut harness, entity, 1);
+    harness
+        .world
+        .get_mut::<AnimationPlayer>(entity)
+        .unwrap()
+        .seek(175.0);
+    // the seek and the first step land in the same frame
+    assert_eq!(play(&mut harness, entity, 5), [6.25, 0.75, 0.25, 0.0, 0.0]);
+    assert_eq!(
+        harness.world.get::<AnimationPlayer>(entity).unwrap().time,
+        0.0
+    );
+}
@@ -0,0 +1,301 @@
+use std::ops::Range;
+
+use bevy_ecs::prelude::Component;
+use mylib::animation::*;
+use proptest::prelude::*;
+
+const FIELDS: usize = 64;
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Fields([f32; FIELDS]);
+
+#[derive(Clone, Debug)]
+struct FieldLens {
+    field: usize,
+    start: f32,
+    end: f32,
+}
+
+impl TweenTarget<Fields> for FieldLens {
+    fn lerp(&mut self, target: &mut Fields, ratio: f32) {
+        target.0[self.field] = self.start + (self.end - self.start) * ratio;
+    }
+
+    fn writes(&self, target: &mut Fields) -> Option<Range<usize>> {
+        field_bytes(target, |target| Some(&mut target.0[self.field]))
+    }
+}
+
+// the shape of a timeline, fields are handed out when it gets built so that
+// parallel branches never write the same one
+#[derive(Clone, Debug)]
+enum Node {
+    Tween {
+        ease: EasingFunction,
+        duration: f32,
+        start: f32,
+        end: f32,
+    },
+    Delay(f32),
+    Seq {
+        children: Vec<Node>,
+        repeat: u32,
+        yoyo: bool,
+        repeat_delay: f32,
+    },
+    Par(Vec<(f32, Node)>),
+}
+
+fn node() -> impl Strategy<Value = Node> {
+    let ease = prop_oneof![
+        Just(EasingFunction::Linear),
+        Just(EasingFunction::QuadraticIn),
+        Just(EasingFunction::CubicInOut),
+        Just(EasingFunction::BounceOut),
+        Just(EasingFunction::BackInOut),
+        Just(EasingFunction::Steps(4, StepPosition::JumpEnd)),
+    ];
+    let duration = prop_oneof![Just(0.0_f32), 1.0_f32..200.0];
+    let leaf = prop_oneof![
+        (ease, duration, -10.0_f32..10.0, -10.0_f32..10.0).prop_map(
+            |(ease, duration, start, end)| Node::Tween {
+                ease,
+                duration,
+                start,
+                end,
+            }
+        ),
+        (0.0_f32..100.0).prop_map(Node::Delay),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            (
+                prop::collection::vec(inner.clone(), 1..4),
+                0_u32..3,
+                any::<bool>(),
+                prop_oneof![Just(0.0_f32), 0.0_f32..50.0],
+            )
+                .prop_map(|(children, repeat, yoyo, repeat_delay)| Node::Seq {
+                    children,
+                    repeat,
+                    yoyo,
+                    repeat_delay,
+                }),
+            prop::collection::vec((0.0_f32..100.0, inner), 1..4).prop_map(Node::Par),
+        ]
+    })
+}
+
+struct Builder {
+    next_field: usize,
+}
+
+impl Builder {
+    fn fresh_field(&mut self) -> usize {
+        self.next_field += 1;
+        self.next_field % FIELDS
+    }
+
+    fn seq(&mut self, children: &[Node], field: usize) -> Seq<Fields> {
+        let mut seq = Seq::new();
+        for child in children {
+            match child {
+                Node::Tween {
+                    ease,
+                    duration,
+                    start,
+                    end,
+                } => {
+                    seq.then(Tween::new(
+                        *ease,
+                        *duration,
+                        FieldLens {
+                            field,
+                            start: *start,
+                            end: *end,
+                        },
+                    ));
+                }
+                Node::Delay(duration) => {
+                    seq.then_delay(*duration);
+                }
+                Node::Seq { .. } => {
+                    seq.then(self.node_seq(child, field));
+                }
+                Node::Par(children) => {
+                    seq.then(self.par(children));
+                }
+            }
+        }
+        seq
+    }
+
+    fn node_seq(&mut self, node: &Node, field: usize) -> Seq<Fields> {
+        match node {
+            Node::Seq {
+                children,
+                repeat,
+                yoyo,
+                repeat_delay,
+            } => {
+                let mut seq = self.seq(children, field);
+                seq.repeat(Repeat::Count(*repeat))
+                    .yoyo(*yoyo)
+                    .repeat_delay(*repeat_delay);
+                seq
+            }
+            leaf => self.seq(std::slice::from_ref(leaf), field),
+        }
+    }
+
+    fn par(&mut self, children: &[(f32, Node)]) -> Par<Fields> {
+        let mut par = Par::new();
+        for (offset, child) in children {
+            let field = self.fresh_field();
+            par.add_at(*offset, self.node_seq(child, field));
+        }
+        par
+    }
+
+    fn animate(&mut self, sequences: &[Node]) -> AnimateComponent<Fields> {
+        let mut animate = AnimateComponent::new();
+        for seq in sequences {
+            let field = self.fresh_field();
+            animate.add_seq(self.node_seq(seq, field));
+        }
+        animate
+    }
+}
+
+fn timeline() -> impl Strategy<Value = AnimateComponent<Fields>> {
+    prop::collection::vec(node(), 1..4)
+        .prop_map(|sequences| Builder { next_field: 0 }.animate(&sequences))
+}
+
+fn assert_same(played: &Fields, sought: &Fields) -> Result<(), TestCaseError> {
+    for (field, (a, b)) in played.0.iter().zip(sought.0.iter()).enumerate() {
+        prop_assert!(
+            (a - b).abs() < 1e-3,
+            "field {} is {} when played, {} when sought",
+            field,
+            a,
+            b
+        );
+    }
+    Ok(())
+}
+
+fn fields(values: &[f32]) -> Fields {
+    let mut fields = Fields([-1.0; FIELDS]);
+    fields.0[..values.len()].copy_from_slice(values);
+    fields
+}
+
+fn play(animate: &mut AnimateComponent<Fields>, target: &mut Fields, times: &[f32]) {
+    for time in times {
+        for seq in &mut animate.sequences {
+            seq.sample(target, *time);
+        }
+    }
+}
+
+fn sought(animate: &AnimateComponent<Fields>, time: f32) -> Fields {
+    let mut animate = animate.clone();
+    let mut target = Fields([0.0; FIELDS]);
+    animate.seek(&mut target, time);
+    target
+}
+
+proptest! {
+    #[test]
+    fn playing_forward_matches_seeking(
+        animate in timeline(),
+        steps in prop::collection::vec(0.0_f32..120.0, 1..40),
+    ) {
+        let mut played = animate.clone();
+        let mut target = Fields([0.0; FIELDS]);
+
+        let mut times = vec![0.0];
+        for step in steps {
+            times.push(times.last().unwrap() + step);
+        }
+        play(&mut played, &mut target, &times);
+
+        assert_same(&target, &sought(&animate, *times.last().unwrap()))?;
+    }
+
+    #[test]
+    fn scrubbing_back_and_forth_matches_seeking(
+        animate in timeline(),
+        steps in prop::collection::vec(-150.0_f32..150.0, 1..40),
+    ) {
+        let mut played = animate.clone();
+        let mut target = Fields([0.0; FIELDS]);
+
+        let mut times = vec![0.0_f32];
+        for step in steps {
+            times.push((times.last().unwrap() + step).max(0.0));
+        }
+        play(&mut played, &mut target, &times);
+
+        assert_same(&target, &sought(&animate, *times.last().unwrap()))?;
+    }
+
+    #[test]
+    fn seeking_does_not_depend_on_history(
+        animate in timeline(),
+        history in prop::collection::vec(0.0_f32..2000.0, 0..10),
+        time in 0.0_f32..2000.0,
+    ) {
+        let mut animate_after_history = animate.clone();
+        let mut target = Fields([0.0; FIELDS]);
+        for past in history {
+            animate_after_history.seek(&mut target, past);
+        }
+        animate_after_history.seek(&mut target, time);
+
+        assert_same(&target, &sought(&animate, time))?;
+    }
+}
+
+#[test]
+fn seek_lands_finished_tweens_on_their_end() {
+    let mut seq = Seq::new();
+    seq.then(Tween::new(
+        EasingFunction::QuadraticIn,
+        100.0,
+        FieldLens {
+            field: 0,
+            start: 0.0,
+            end: 5.0,
+        },
+    ))
+    .then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        FieldLens {
+            field: 1,
+            start: 1.0,
+            end: 2.0,
+        },
+    ))
+    .then(Tween::new(
+        EasingFunction::Linear,
+        100.0,
+        FieldLens {
+            field: 2,
+            start: 3.0,
+            end: 4.0,
+        },
+    ));
+
+    let mut animate = AnimateComponent::new();
+    animate.add_seq(seq);
+
+    let mut target = Fields([-1.0; FIELDS]);
+    animate.seek(&mut target, 150.0);
+    assert_same(&target, &fields(&[5.0, 1.5, 3.0])).unwrap();
+
+    animate.seek(&mut target, 10.0);
+    assert_same(&target, &fields(&[0.05, 1.0, 3.0])).unwrap();
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::{value, Harness, Value};
+use mylib::animation::*;
+use mylib::spring::*;
+
+const UNDER: SpringConfig = SpringConfig::WOBBLY;
+const CRITICAL: SpringConfig = SpringConfig::new(100.0, 20.0, 1.0);
+const OVER: SpringConfig = SpringConfig::new(100.0, 50.0, 2.0);
+
+// semi-implicit euler in 0.01 ms steps
+fn integrate(config: SpringConfig, from: f32, velocity: f32, to: f32, ms: f32) -> (f32, f32) {
+    let dt = 1e-5_f64;
+    let (mut x, mut v) = (from as f64, velocity as f64);
+    for _ in 0..(ms as f64 / 1000.0 / dt).round() as usize {
+        let force = -config.stiffness as f64 * (x - to as f64) - config.damping as f64 * v;
+        v += force / config.mass as f64 * dt;
+        x += v * dt;
+    }
+    (x as f32, v as f32)
+}
+
+#[test]
+fn follows_the_equation_of_motion() {
+    for config in [UNDER, CRITICAL, OVER] {
+        let spring = Spring::new(value, config, 0.0, 1.0).with_velocity(2.0);
+        for ms in [50.0, 200.0, 400.0] {
+            let (x, v) = spring.state_at(ms);
+            let (ex, ev) = integrate(config, 0.0, 2.0, 1.0, ms);
+            assert!((x - ex).abs() < 1e-3, "{:?} {}ms", config, ms);
+            assert!((v - ev).abs() < 1e-2, "{:?} {}ms", config, ms);
+        }
+    }
+}
+
+#[test]
+fn settles_by_its_duration() {
+    for config in [UNDER, CRITICAL, OVER] {
+        let mut spring = Spring::new(value, config, 0.0, 1.0);
+        let duration = spring.duration();
+        assert!(duration.is_finite() && duration > 0.0);
+        let (x, _) = spring.state_at(duration * 0.999);
+        assert!((x - 1.0).abs() < 0.01, "{:?}: {}", config, x);
+
+        let mut v = Value(0.0);
+        spring.sample(&mut v, duration);
+        assert_eq!(v.0, 1.0);
+    }
+    assert_eq!(
+        SpringConfig::new(100.0, 0.0, 1.0).settle_time(0.001),
+        f32::INFINITY
+    );
+}
+
+#[test]
+fn settle_time_scales_with_displacement_and_velocity() {
+    for config in [UNDER, CRITICAL, OVER] {
+        let unit = Spring::new(value, config, 0.0, 1.0).duration();
+        let far = Spring::new(value, config, 0.0, 100.0);
+        let fast = Spring::new(value, config, 0.0, 1.0).with_velocity(50.0);
+        for (spring, to) in [(&far, 100.0), (&fast, 1.0)] {
+            let duration = Tweenable::<Value>::duration(spring);
+            assert!(duration > unit, "{:?}", config);
+            let (x, _) = spring.state_at(duration * 0.999);
+            assert!((x - to).abs() < 0.01, "{:?}: {}", config, x);
+        }
+    }
+
+    // already at rest on its target
+    let still = Spring::new(value, SpringConfig::DEFAULT, 1.0, 1.0);
+    assert_eq!(Tweenable::<Value>::duration(&still), 0.0);
+}
+
+#[test]
+fn only_underdamped_springs_overshoot() {
+    let peak = |config| {
+        let spring = Spring::new(value, config, 0.0, 1.0);
+        (0..100)
+            .map(|i| spring.state_at(i as f32 * 10.0).0)
+            .fold(f32::MIN, f32::max)
+    };
+    assert!(peak(UNDER) > 1.1);
+    assert!(peak(CRITICAL) <= 1.0);
+    assert!(peak(OVER) <= 1.0);
+}
+
+#[test]
+fn retargeting_keeps_the_velocity() {
+    let mut spring = Spring::new(value, SpringConfig::DEFAULT, 0.0, 1.0);
+    let before = spring.state_at(100.0);
+    spring.retarget(100.0, -1.0);
+    let after = spring.state_at(100.0);
+    assert!((before.0 - after.0).abs() < 1e-6);
+    assert!((before.1 - after.1).abs() < 1e-4);
+    assert!(after.1 > 0.0);
+    assert_eq!(spring.target(), -1.0);
+    assert!(spring.duration() > 100.0);
+
+    // still a function of time, before the retarget nothing changed
+    assert_eq!(
+        spring.state_at(50.0),
+        Spring::new(value, SpringConfig::DEFAULT, 0.0, 1.0).state_at(50.0)
+    );
+    // a retarget in the past replaces the later ones
+    spring.retarget(50.0, 2.0);
+    assert_eq!(spring.target(), 2.0);
+}
+
+#[test]
+fn reports_its_settle_time_and_can_be_retargeted_in_a_sequence() {
+    let mut animate = AnimateComponent::new();
+    let mut seq = Seq::new();
+    seq.then_delay(100.0)
+        .then(Spring::new(value, SpringConfig::STIFF, 0.0, 10.0));
+    animate.add_seq(seq);
+    let settle = SpringConfig::STIFF.settle_time_from(0.001, 10.0, 0.0);
+    assert_eq!(animate.total_time(), 100.0 + settle);
+
+    // the clock stays where the test puts it
+    let mut harness = Harness::new(0.0);
+    let entity = harness.spawn("spring", Value(0.0), animate);
+    harness.step(1);
+    assert_eq!(harness.info().totalTime, 100.0 + settle);
+
+    harness.info().currentTime = 200.0;
+    harness.step(1);
+    let mut animate = harness
+        .world
+        .get_mut::<AnimateComponent<Value>>(entity)
+        .unwrap();
+    let spring = animate.sequences[0].tweens_mut()[1]
+        .downcast_mut::<Spring<Value, f32>>()
+        .unwrap();
+    spring.retarget(200.0, 0.0);
+    // settling again from the retarget, from where it was
+    let (x, v) = spring.state_at(200.0);
+    let settle = SpringConfig::STIFF.settle_time_from(0.001, x.abs(), v.abs());
+    assert_eq!(animate.total_time(), 200.0 + settle);
+
+    // the retarget changed the component, so the clock makes room for it
+    harness.step(1);
+    assert_eq!(harness.info().totalTime, 200.0 + settle);
+    harness.info().currentTime = 200.0 + settle;
+    let value = harness.step(1).target(entity).0;
+    assert!(value.abs() < 0.01, "{}", value);
+}
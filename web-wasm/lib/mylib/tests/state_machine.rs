@@ -0,0 +1,127 @@
+use bevy_ecs::prelude::*;
+use mylib::animation::*;
+use mylib::clip::*;
+use mylib::state_machine::*;
+use mylib::Transform;
+
+const JSON: &str = r#"{
+    "initial": "idle",
+    "states": { "idle": "idle", "walk": "walk", "jump": "jump" },
+    "params": { "speed": { "Float": 0.0 }, "jump": { "Trigger": false } },
+    "transitions": [
+        { "from": "idle", "to": "walk", "conditions": [{ "Greater": ["speed", 0.1] }] },
+        { "from": "walk", "to": "idle", "duration": 100.0, "conditions": [{ "Less": ["speed", 0.1] }] },
+        { "to": "jump", "conditions": [{ "Triggered": "jump" }] },
+        { "from": "jump", "to": "idle", "conditions": ["Finished"] }
+    ]
+}"#;
+
+fn clip(ron: &str) -> Clip {
+    Clip::from_ron(ron).unwrap()
+}
+
+fn world() -> (World, Schedule, Entity) {
+    let mut world = World::new();
+    world.insert_resource(AnimationSystemInfo::default());
+    let mut library = ClipLibrary::default();
+    library.insert(clip(
+        r#"(name: "idle", sequences: [(steps: [Tween((duration: 100, ease: Linear, target: PositionX(from: 0, to: 0)))], repeat: Infinite)])"#,
+    ));
+    library.insert(clip(
+        r#"(name: "walk", sequences: [(steps: [Tween((duration: 100, ease: Linear, target: PositionX(from: 10, to: 10)))], repeat: Infinite)])"#,
+    ));
+    library.insert(clip(
+        r#"(name: "jump", sequences: [(steps: [Tween((duration: 100, ease: Linear, target: PositionY(from: 0, to: 4)))])])"#,
+    ));
+    world.insert_resource(library);
+
+    let machine = AnimationStateMachine::from_json(JSON).unwrap();
+    let animate = AnimateComponent::<Transform>::new();
+    let entity = world.spawn((Transform::default(), animate, machine)).id();
+
+    let mut schedule = Schedule::new();
+    add_animation_events(&mut world, &mut schedule);
+    schedule.add_system(animation_state_machine_sys.before(animation_sys::<Transform>));
+    schedule.add_system(animation_sys::<Transform>);
+    (world, schedule, entity)
+}
+
+fn frame(world: &mut World, schedule: &mut Schedule, time: f32) -> Transform {
+    world.resource_mut::<AnimationSystemInfo>().currentTime = time;
+    schedule.run(world);
+    world.query::<&Transform>().single(world).clone()
+}
+
+fn machine(world: &mut World, entity: Entity) -> Mut<'_, AnimationStateMachine> {
+    world.get_mut::<AnimationStateMachine>(entity).unwrap()
+}
+
+#[test]
+fn parameters_keep_their_type() {
+    let mut machine = AnimationStateMachine::new("idle", "idle");
+    machine.add_bool("grounded", true).add_float("speed", 0.0);
+    machine.set_float("speed", 2.0).unwrap();
+    assert_eq!(machine.param("speed"), Some(Param::Float(2.0)));
+
+    let err = machine.set_bool("speed", true).unwrap_err();
+    assert_eq!(err.to_string(), r#"parameter "speed" is a float"#);
+    let err = machine.trigger("jump").unwrap_err();
+    assert_eq!(err.to_string(), r#"no parameter named "jump""#);
+    assert_eq!(machine.param("grounded"), Some(Param::Bool(true)));
+
+    // looking one up leaves it alone
+    machine.add_trigger("jump");
+    assert_eq!(machine.lookup_param("jump").unwrap(), Param::Trigger(false));
+    assert_eq!(machine.param("jump"), Some(Param::Trigger(false)));
+    let err = machine.lookup_param("fly").unwrap_err();
+    assert_eq!(err.to_string(), r#"no parameter named "fly""#);
+}
+
+#[test]
+fn machines_are_checked_when_loaded() {
+    let machine = AnimationStateMachine::from_json(JSON).unwrap();
+    assert_eq!(machine.state(), "idle");
+    assert_eq!(machine.transitions[2].from, None);
+    let written = serde_json::to_string(&machine).unwrap();
+    assert_eq!(AnimationStateMachine::from_json(&written).unwrap(), machine);
+
+    let err = AnimationStateMachine::from_json(&JSON.replace(r#""to": "jump""#, r#""to": "fly""#))
+        .unwrap_err();
+    assert_eq!(err.to_string(), r#"no state named "fly""#);
+    let err =
+        AnimationStateMachine::from_json(&JSON.replace(r#"["speed", 0.1]"#, r#"["sped", 0.1]"#))
+            .unwrap_err();
+    assert_eq!(err.to_string(), r#"no parameter named "sped""#);
+}
+
+#[test]
+fn parameters_drive_the_transitions() {
+    let (mut world, mut schedule, entity) = world();
+    assert_eq!(frame(&mut world, &mut schedule, 0.0).position.x, 0.0);
+    assert_eq!(frame(&mut world, &mut schedule, 50.0).position.x, 0.0);
+
+    machine(&mut world, entity).set_float("speed", 1.0).unwrap();
+    assert_eq!(frame(&mut world, &mut schedule, 100.0).position.x, 10.0);
+    assert_eq!(machine(&mut world, entity).state(), "walk");
+
+    // back to idle blends over 100 ms
+    machine(&mut world, entity).set_float("speed", 0.0).unwrap();
+    assert_eq!(frame(&mut world, &mut schedule, 200.0).position.x, 10.0);
+    assert_eq!(frame(&mut world, &mut schedule, 250.0).position.x, 5.0);
+    assert_eq!(frame(&mut world, &mut schedule, 300.0).position.x, 0.0);
+
+    // triggers are used up by the transition they fire
+    machine(&mut world, entity).trigger("jump").unwrap();
+    assert_eq!(frame(&mut world, &mut schedule, 400.0).position.y, 0.0);
+    assert_eq!(machine(&mut world, entity).state(), "jump");
+    assert_eq!(
+        machine(&mut world, entity).param("jump"),
+        Some(Param::Trigger(false))
+    );
+    assert_eq!(frame(&mut world, &mut schedule, 450.0).position.y, 2.0);
+    assert_eq!(machine(&mut world, entity).state(), "jump");
+
+    // and the jump goes back to idle once its clip is done
+    frame(&mut world, &mut schedule, 500.0);
+    assert_eq!(machine(&mut world, entity).state(), "idle");
+}